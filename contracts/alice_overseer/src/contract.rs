@@ -107,7 +107,10 @@ fn execute_initiate_migrate(
         return Err(ContractError::UnregisteredContract());
     }
 
-    if migrate_timelocks_read(deps.storage).may_load(contract_addr_bytes)? != None {
+    if migrate_timelocks_read(deps.storage)
+        .may_load(contract_addr_bytes)?
+        .is_some()
+    {
         return Err(ContractError::ExistingTimelock());
     }
 
@@ -18,19 +18,19 @@ pub struct Config {
     pub timelock_duration: Duration,
 }
 
-pub fn config_mut(storage: &mut dyn Storage) -> Singleton<Config> {
+pub fn config_mut(storage: &mut dyn Storage) -> Singleton<'_, Config> {
     singleton(storage, CONFIG_KEY)
 }
 
-pub fn config_read(storage: &dyn Storage) -> ReadonlySingleton<Config> {
+pub fn config_read(storage: &dyn Storage) -> ReadonlySingleton<'_, Config> {
     singleton_read(storage, CONFIG_KEY)
 }
 
-pub fn contracts_mut(storage: &mut dyn Storage) -> Bucket<bool> {
+pub fn contracts_mut(storage: &mut dyn Storage) -> Bucket<'_, bool> {
     bucket(storage, CONTRACTS_KEY)
 }
 
-pub fn contracts_read(storage: &dyn Storage) -> ReadonlyBucket<bool> {
+pub fn contracts_read(storage: &dyn Storage) -> ReadonlyBucket<'_, bool> {
     bucket_read(storage, CONTRACTS_KEY)
 }
 
@@ -41,10 +41,10 @@ pub struct MigrateTimelock {
     pub msg: Binary,
 }
 
-pub fn migrate_timelocks_mut(storage: &mut dyn Storage) -> Bucket<MigrateTimelock> {
+pub fn migrate_timelocks_mut(storage: &mut dyn Storage) -> Bucket<'_, MigrateTimelock> {
     Bucket::multilevel(storage, &[TIMELOCKS_KEY, MIGRATE_KEY])
 }
 
-pub fn migrate_timelocks_read(storage: &dyn Storage) -> ReadonlyBucket<MigrateTimelock> {
+pub fn migrate_timelocks_read(storage: &dyn Storage) -> ReadonlyBucket<'_, MigrateTimelock> {
     ReadonlyBucket::multilevel(storage, &[TIMELOCKS_KEY, MIGRATE_KEY])
 }
@@ -3,13 +3,20 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use cw20::{BalanceResponse, TokenInfoResponse};
+use cw20::{
+    AllAccountsResponse, BalanceResponse, DownloadLogoResponse, MarketingInfoResponse,
+    TokenInfoResponse,
+};
 
 use alice_terra_token::msg::{
-    ExchangeRateResponse, ExecuteMsg, InstantiateMsg, MetaTx, MigrateMsg, QueryMsg,
-    RelayNonceResponse,
+    ApyResponse, DepositCapacityResponse, EarningsResponse, EmergencyExitResponse,
+    ExchangeRateResponse, ExecuteMsg, FeeExemptionsResponse, FeeScheduleResponse,
+    HolderCountResponse, InstantiateMsg, MetaTx, MigrateMsg, OperatorsResponse,
+    PendingOwnerResponse, QueryMsg, RateHistoryResponse, ReferralEarningsResponse,
+    ReferralsResponse, RelayNonceResponse, SchedulesResponse, SkimmedTotalResponse,
+    SolvencyResponse, TotalSupplyResponse, WithdrawalTicketsResponse,
 };
-use alice_terra_token::state::Config;
+use alice_terra_token::state::{Config, PauseState};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -28,5 +35,26 @@ fn main() {
     export_schema(&schema_for!(TokenInfoResponse), &out_dir);
     export_schema(&schema_for!(ExchangeRateResponse), &out_dir);
     export_schema(&schema_for!(RelayNonceResponse), &out_dir);
+    export_schema(&schema_for!(WithdrawalTicketsResponse), &out_dir);
+    export_schema(&schema_for!(SchedulesResponse), &out_dir);
+    export_schema(&schema_for!(DepositCapacityResponse), &out_dir);
+    export_schema(&schema_for!(SolvencyResponse), &out_dir);
+    export_schema(&schema_for!(SkimmedTotalResponse), &out_dir);
+    export_schema(&schema_for!(PauseState), &out_dir);
+    export_schema(&schema_for!(EmergencyExitResponse), &out_dir);
+    export_schema(&schema_for!(PendingOwnerResponse), &out_dir);
+    export_schema(&schema_for!(OperatorsResponse), &out_dir);
+    export_schema(&schema_for!(EarningsResponse), &out_dir);
+    export_schema(&schema_for!(FeeScheduleResponse), &out_dir);
+    export_schema(&schema_for!(RateHistoryResponse), &out_dir);
+    export_schema(&schema_for!(ApyResponse), &out_dir);
+    export_schema(&schema_for!(FeeExemptionsResponse), &out_dir);
+    export_schema(&schema_for!(ReferralsResponse), &out_dir);
+    export_schema(&schema_for!(ReferralEarningsResponse), &out_dir);
+    export_schema(&schema_for!(TotalSupplyResponse), &out_dir);
+    export_schema(&schema_for!(AllAccountsResponse), &out_dir);
+    export_schema(&schema_for!(HolderCountResponse), &out_dir);
+    export_schema(&schema_for!(MarketingInfoResponse), &out_dir);
+    export_schema(&schema_for!(DownloadLogoResponse), &out_dir);
     export_schema(&schema_for!(Config), &out_dir);
 }
@@ -1,22 +1,58 @@
+use cosmwasm_bignumber::Decimal256;
 use cosmwasm_std::{
     entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
     StdResult, Uint128,
 };
 use cw2::set_contract_version;
-use cw20_base::contract::{execute_send, execute_transfer, query_balance, query_token_info};
+use cw20_base::contract::{query_balance, query_download_logo, query_token_info};
+use cw20_base::enumerable::query_all_accounts;
 use cw20_base::state::{MinterData, TokenInfo, TOKEN_INFO};
 
+use crate::earnings::{execute_send_with_principal, execute_transfer_with_principal};
+use crate::emergency_exit::{execute_emergency_exit, handle_reply_emergency_exit};
+use crate::enumerable::execute_recount_holders;
 use crate::error::ContractError;
 use crate::execute::{
-    execute_deposit_stable, execute_deposit_stable_authorized, execute_redeem_stable,
-    handle_reply_deposit_stable, handle_reply_redeem_stable, DEPOSIT_STABLE_REPLY_ID,
-    REDEEM_STABLE_REPLY_ID,
+    execute_deposit_stable, execute_deposit_stable_authorized,
+    execute_deposit_stable_authorized_batch, execute_redeem_stable, execute_update_config,
+    handle_reply_deposit_stable, handle_reply_redeem_stable, handle_reply_swap_deposit,
+    handle_reply_swap_redeem, ConfigUpdate, RedeemOptions, DEPOSIT_STABLE_REPLY_ID,
+    EMERGENCY_EXIT_REPLY_ID, PROCESS_WITHDRAWALS_REPLY_ID, REDEEM_STABLE_REPLY_ID,
+    SWAP_DEPOSIT_REPLY_ID, SWAP_REDEEM_REPLY_ID,
 };
-use crate::migrate::migrate_config;
+use crate::fees::{execute_update_fee_exemptions, validate_fee_recipients};
+use crate::marketing::{execute_update_marketing, execute_upload_logo};
+#[cfg(feature = "snapshot")]
+use crate::migrate::migrate_snapshots;
+use crate::migrate::{migrate_config, migrate_holder_count};
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::query::query_relay_nonce;
+use crate::operators::execute_update_operators;
+use crate::ownership::{
+    execute_accept_ownership, execute_cancel_ownership_proposal, execute_propose_new_owner,
+};
+use crate::pause::{assert_not_paused, execute_pause, execute_unpause, load_pause_state};
+use crate::query::{
+    query_apy, query_deposit_capacity, query_earnings, query_emergency_exit, query_fee_exemptions,
+    query_fee_schedule, query_holder_count, query_marketing_info, query_operators,
+    query_pending_owner, query_rate_history, query_referral_earnings, query_referrals,
+    query_relay_nonce, query_schedules, query_skimmed_total, query_solvency,
+    query_withdrawal_tickets,
+};
+#[cfg(feature = "snapshot")]
+use crate::query::{query_balance_at_height, query_total_supply_at_height};
+use crate::rate_history::execute_record_rate;
 use crate::relay::execute_relay;
-use crate::state::{config_read, save_config, Config};
+use crate::rescue::{execute_rescue_cw20, execute_rescue_native};
+use crate::schedules::{
+    execute_cancel_schedule, execute_create_schedule, execute_process_schedules,
+};
+use crate::skim::execute_skim_surplus;
+#[cfg(feature = "snapshot")]
+use crate::snapshot::init_snapshots;
+use crate::state::{config_read, holder_count_mut, save_config, Config, PauseState};
+use crate::withdrawal_queue::{
+    execute_cancel_withdrawal, execute_process_withdrawals, handle_reply_process_withdrawals,
+};
 
 const CONTRACT_NAME: &str = "crates.io:alice-terra-token";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -40,10 +76,19 @@ pub fn instantiate(
         }),
     };
     TOKEN_INFO.save(deps.storage, &data)?;
+    holder_count_mut(deps.storage).save(&0)?;
+
+    #[cfg(feature = "snapshot")]
+    init_snapshots(deps.storage, env.block.height)?;
 
     // initialize CW2
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    let guardian = msg
+        .guardian
+        .map(|guardian| deps.api.addr_validate(&guardian))
+        .transpose()?;
+
     // contract configuration information
     save_config(
         deps.storage,
@@ -53,6 +98,19 @@ pub fn instantiate(
             money_market_addr: deps.api.addr_validate(&msg.money_market_addr)?,
             aterra_token_addr: deps.api.addr_validate(&msg.aterra_token_addr)?,
             redeem_fee_ratio: msg.redeem_fee_ratio,
+            swap_denoms: msg.swap_denoms.unwrap_or_default(),
+            address_deposit_cap: msg.address_deposit_cap,
+            total_deposit_cap: msg.total_deposit_cap,
+            deposit_fee_ratio: msg.deposit_fee_ratio.unwrap_or_else(Decimal256::zero),
+            fee_recipients: validate_fee_recipients(
+                deps.api,
+                msg.fee_recipients.unwrap_or_default(),
+            )?,
+            redeem_fee_tiers: msg.redeem_fee_tiers.unwrap_or_default(),
+            referral_share: msg.referral_share.unwrap_or_else(Decimal256::zero),
+            guardian,
+            tax_policy: msg.tax_policy.unwrap_or_default(),
+            yield_backend: msg.yield_backend.unwrap_or_default(),
         },
     )?;
 
@@ -66,6 +124,8 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, &msg)?;
+
     match msg {
         ExecuteMsg::Relay {
             meta_tx,
@@ -73,18 +133,124 @@ pub fn execute(
             public_key,
         } => execute_relay(deps, env, info, meta_tx, signature, public_key),
         ExecuteMsg::DepositStableAuthorized {
-            recipient, amount, ..
-        } => execute_deposit_stable_authorized(deps, env, info, recipient, amount),
-        ExecuteMsg::DepositStable { recipient } => {
-            execute_deposit_stable(deps, env, info, recipient)
+            recipient,
+            amount,
+            referrer,
+            ..
+        } => execute_deposit_stable_authorized(deps, env, info, recipient, amount, referrer),
+        ExecuteMsg::DepositStableAuthorizedBatch { granter, deposits } => {
+            execute_deposit_stable_authorized_batch(deps, env, info, granter, deposits)
         }
+        ExecuteMsg::DepositStable {
+            recipient,
+            min_stable_amount,
+            referrer,
+        } => execute_deposit_stable(deps, env, info, recipient, min_stable_amount, referrer),
         ExecuteMsg::RedeemStable {
             burn_amount,
             recipient,
-        } => execute_redeem_stable(deps, env, info, burn_amount, recipient),
+            queue_if_illiquid,
+            target_denom,
+            min_target_amount,
+        } => execute_redeem_stable(
+            deps,
+            env,
+            info,
+            burn_amount,
+            recipient,
+            RedeemOptions {
+                queue_if_illiquid: queue_if_illiquid.unwrap_or(false),
+                target_denom,
+                min_target_amount,
+            },
+        ),
+        ExecuteMsg::ProcessWithdrawals { limit } => {
+            execute_process_withdrawals(deps, env, info, limit)
+        }
+        ExecuteMsg::CreateSchedule {
+            amount,
+            interval,
+            end,
+        } => execute_create_schedule(deps, env, info, amount, interval, end),
+        ExecuteMsg::CancelSchedule { id } => execute_cancel_schedule(deps, info, id),
+        ExecuteMsg::ProcessSchedules { limit } => execute_process_schedules(deps, env, info, limit),
+        ExecuteMsg::RecordRate {} => execute_record_rate(deps, env, info),
+        ExecuteMsg::RecountHolders { limit } => execute_recount_holders(deps, env, info, limit),
+        ExecuteMsg::CancelWithdrawal { id } => execute_cancel_withdrawal(deps, env, info, id),
+        ExecuteMsg::ProposeNewOwner { owner, expires } => {
+            execute_propose_new_owner(deps, env, info, owner, expires)
+        }
+        ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, env, info),
+        ExecuteMsg::CancelOwnershipProposal {} => {
+            execute_cancel_ownership_proposal(deps, env, info)
+        }
+        ExecuteMsg::UpdateConfig {
+            redeem_fee_ratio,
+            redeem_fee_tiers,
+            deposit_fee_ratio,
+            referral_share,
+            fee_recipients,
+            deposit_caps,
+            swap_denoms,
+            tax_policy,
+        } => execute_update_config(
+            deps,
+            env,
+            info,
+            ConfigUpdate {
+                redeem_fee_ratio,
+                redeem_fee_tiers,
+                deposit_fee_ratio,
+                referral_share,
+                fee_recipients,
+                deposit_caps,
+                swap_denoms,
+                tax_policy,
+            },
+        ),
+        ExecuteMsg::UpdateFeeExemptions { add, remove } => {
+            execute_update_fee_exemptions(deps, env, info, add, remove)
+        }
+        ExecuteMsg::SkimSurplus { recipient } => execute_skim_surplus(deps, env, info, recipient),
+        ExecuteMsg::RescueCw20 {
+            token,
+            amount,
+            recipient,
+        } => execute_rescue_cw20(deps, env, info, token, amount, recipient),
+        ExecuteMsg::RescueNative {
+            denom,
+            amount,
+            recipient,
+        } => execute_rescue_native(deps, env, info, denom, amount, recipient),
+        ExecuteMsg::Pause {
+            deposits,
+            redeems,
+            transfers,
+            relay,
+        } => execute_pause(
+            deps,
+            env,
+            info,
+            PauseState {
+                deposits,
+                redeems,
+                transfers,
+                relay,
+            },
+        ),
+        ExecuteMsg::Unpause {} => execute_unpause(deps, env, info),
+        ExecuteMsg::EmergencyExit {} => execute_emergency_exit(deps, env, info),
+        ExecuteMsg::UpdateOperators { add, remove } => {
+            execute_update_operators(deps, env, info, add, remove)
+        }
+        ExecuteMsg::UpdateMarketing {
+            project,
+            description,
+        } => execute_update_marketing(deps, env, info, project, description),
+        ExecuteMsg::UploadLogo(logo) => execute_upload_logo(deps, env, info, logo),
         // inherited from cw20-base
         ExecuteMsg::Transfer { recipient, amount } => {
-            Ok(execute_transfer(deps, env, info, recipient, amount)?)
+            execute_transfer_with_principal(deps, env, info, recipient, amount)
         }
         ExecuteMsg::Burn { amount } => execute_redeem_stable(
             deps,
@@ -92,12 +258,13 @@ pub fn execute(
             info.clone(),
             amount,
             Some(info.sender.to_string()),
+            RedeemOptions::default(),
         ),
         ExecuteMsg::Send {
             contract,
             amount,
             msg,
-        } => Ok(execute_send(deps, env, info, contract, amount, msg)?),
+        } => execute_send_with_principal(deps, env, info, contract, amount, msg),
     }
 }
 
@@ -106,23 +273,85 @@ pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, Contract
     match (reply.id, reply.result) {
         (REDEEM_STABLE_REPLY_ID, result) => handle_reply_redeem_stable(deps, env, result),
         (DEPOSIT_STABLE_REPLY_ID, result) => handle_reply_deposit_stable(deps, env, result),
+        (SWAP_DEPOSIT_REPLY_ID, result) => handle_reply_swap_deposit(deps, env, result),
+        (SWAP_REDEEM_REPLY_ID, result) => handle_reply_swap_redeem(deps, env, result),
+        (EMERGENCY_EXIT_REPLY_ID, result) => handle_reply_emergency_exit(deps, env, result),
+        (PROCESS_WITHDRAWALS_REPLY_ID, result) => {
+            handle_reply_process_withdrawals(deps, env, result)
+        }
         _ => Err(StdError::generic_err("invalid reply id or result").into()),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::RelayNonce { address } => to_binary(&query_relay_nonce(deps, address)?),
         QueryMsg::Config {} => to_binary(&config_read(deps.storage).load()?),
+        QueryMsg::DepositCapacity { address } => to_binary(&query_deposit_capacity(deps, address)?),
+        QueryMsg::PauseState {} => to_binary(&load_pause_state(deps.storage)?),
+        QueryMsg::PendingOwner {} => to_binary(&query_pending_owner(deps)?),
+        QueryMsg::EmergencyExit {} => to_binary(&query_emergency_exit(deps)?),
+        QueryMsg::Solvency {} => to_binary(&query_solvency(deps, env)?),
+        QueryMsg::SkimmedTotal {} => to_binary(&query_skimmed_total(deps)?),
+        QueryMsg::Schedules {
+            address,
+            start_after,
+            limit,
+        } => to_binary(&query_schedules(deps, address, start_after, limit)?),
+        QueryMsg::WithdrawalTickets {
+            address,
+            start_after,
+            limit,
+        } => to_binary(&query_withdrawal_tickets(
+            deps,
+            address,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Earnings { address } => to_binary(&query_earnings(deps, env, address)?),
+        QueryMsg::RateHistory { start_after, limit } => {
+            to_binary(&query_rate_history(deps, start_after, limit)?)
+        }
+        QueryMsg::Apy {} => to_binary(&query_apy(deps)?),
+        QueryMsg::FeeSchedule {} => to_binary(&query_fee_schedule(deps)?),
+        QueryMsg::Referrals {
+            referrer,
+            start_after,
+            limit,
+        } => to_binary(&query_referrals(deps, referrer, start_after, limit)?),
+        QueryMsg::ReferralEarnings { referrer } => {
+            to_binary(&query_referral_earnings(deps, referrer)?)
+        }
+        QueryMsg::FeeExemptions { start_after, limit } => {
+            to_binary(&query_fee_exemptions(deps, start_after, limit)?)
+        }
+        QueryMsg::Operators { start_after, limit } => {
+            to_binary(&query_operators(deps, env, start_after, limit)?)
+        }
         // inherited from cw20-base
         QueryMsg::TokenInfo {} => to_binary(&query_token_info(deps)?),
         QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
+        QueryMsg::AllAccounts { start_after, limit } => {
+            to_binary(&query_all_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::HolderCount {} => to_binary(&query_holder_count(deps)?),
+        QueryMsg::MarketingInfo {} => to_binary(&query_marketing_info(deps)?),
+        QueryMsg::DownloadLogo {} => to_binary(&query_download_logo(deps)?),
+        #[cfg(feature = "snapshot")]
+        QueryMsg::BalanceAtHeight { address, height } => {
+            to_binary(&query_balance_at_height(deps, address, height)?)
+        }
+        #[cfg(feature = "snapshot")]
+        QueryMsg::TotalSupplyAtHeight { height } => {
+            to_binary(&query_total_supply_at_height(deps, height)?)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
+#[cfg_attr(not(feature = "snapshot"), allow(unused_variables))]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
     if let Some(symbol) = msg.symbol.clone() {
         TOKEN_INFO.update(deps.storage, |mut token_info| -> StdResult<_> {
             token_info.symbol = symbol;
@@ -133,6 +362,11 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response>
     // update CW2 version info
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    migrate_holder_count(deps.storage)?;
+
+    #[cfg(feature = "snapshot")]
+    migrate_snapshots(deps.storage, env.block.height)?;
+
     migrate_config(deps, msg)?;
 
     Ok(Response::default())
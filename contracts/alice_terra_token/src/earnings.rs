@@ -0,0 +1,73 @@
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{
+    Addr, Binary, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128,
+};
+
+use crate::emergency_exit::valuation_exchange_rate;
+use crate::error::ContractError;
+use crate::snapshot::{execute_send, execute_transfer};
+use crate::state::PRINCIPALS;
+
+// Fees, relay tips & withdrawal escrow move aliceUST without principal, so fees
+// lower the payer's yield and count as yield for the fee recipients.
+
+pub fn record_deposit(storage: &mut dyn Storage, address: &Addr, amount: Uint128) -> StdResult<()> {
+    PRINCIPALS.update(storage, address, |principal| -> StdResult<_> {
+        let mut principal = principal.unwrap_or_default();
+        principal.deposited += amount;
+        Ok(principal)
+    })?;
+    Ok(())
+}
+
+pub fn record_withdrawal(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    PRINCIPALS.update(storage, address, |principal| -> StdResult<_> {
+        let mut principal = principal.unwrap_or_default();
+        principal.withdrawn += amount;
+        Ok(principal)
+    })?;
+    Ok(())
+}
+
+/// Moves principal with amount aliceUST from sender to recipient, valued at the valuation exchange rate
+fn record_transfer(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    recipient: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    let recipient = deps.api.addr_validate(recipient)?;
+    let exchange_rate = valuation_exchange_rate(deps.as_ref(), env.block.height)?;
+    let value = Uint128::from(exchange_rate * Uint256::from(amount));
+
+    record_withdrawal(deps.storage, sender, value)?;
+    record_deposit(deps.storage, &recipient, value)
+}
+
+pub fn execute_transfer_with_principal(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    record_transfer(deps.branch(), &env, &info.sender, &recipient, amount)?;
+    execute_transfer(deps, env, info, recipient, amount)
+}
+
+pub fn execute_send_with_principal(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    record_transfer(deps.branch(), &env, &info.sender, &contract, amount)?;
+    execute_send(deps, env, info, contract, amount, msg)
+}
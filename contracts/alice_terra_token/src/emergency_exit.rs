@@ -0,0 +1,164 @@
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, ContractResult, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Storage, SubMsgExecutionResponse, Uint128,
+};
+use cw20_base::state::TOKEN_INFO;
+
+use crate::earnings::record_withdrawal;
+use crate::error::ContractError;
+use crate::execute::EMERGENCY_EXIT_REPLY_ID;
+use crate::query::query_native_balance;
+use crate::snapshot::execute_burn;
+use crate::state::{
+    config_read, emergency_exit_mut, emergency_exit_read, pending_emergency_exit_mut, Config,
+    EmergencyExit, PendingEmergencyExit,
+};
+use crate::utils::deduct_tax;
+use crate::yield_strategy::{load_yield_strategy, yield_strategy};
+
+pub fn is_exited(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(emergency_exit_read(storage).may_load()?.is_some())
+}
+
+/// Exchange rate to value aliceUST, frozen at the UST received per aUST after the
+/// emergency exit so Anchor is no longer queried
+pub fn valuation_exchange_rate(deps: Deps, block_height: u64) -> StdResult<Decimal256> {
+    match emergency_exit_read(deps.storage).may_load()? {
+        Some(exit) if exit.aterra_amount.is_zero() => Ok(Decimal256::one()),
+        Some(exit) => Ok(Decimal256::from_ratio(
+            Uint256::from(exit.stable_amount),
+            Uint256::from(exit.aterra_amount),
+        )),
+        None => load_yield_strategy(deps.storage)?.query_exchange_rate(deps, block_height),
+    }
+}
+
+/// Rejects operations that need Anchor after the emergency exit
+pub fn assert_not_exited(storage: &dyn Storage) -> Result<(), ContractError> {
+    if is_exited(storage)? {
+        return Err(ContractError::EmergencyExited {});
+    }
+    Ok(())
+}
+
+/// Redeems all aUST held from Anchor. Afterwards aliceUST is redeemed pro-rata
+/// from the UST held. Only executable by owner or guardian
+pub fn execute_emergency_exit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    if info.sender != config.owner && Some(&info.sender) != config.guardian.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+    assert_not_exited(deps.storage)?;
+
+    let strategy = yield_strategy(&config);
+    let aterra_amount = strategy.query_position(deps.as_ref(), env.contract.address.clone())?;
+
+    // Nothing to redeem, the money market rejects zero amounts
+    if aterra_amount.is_zero() {
+        emergency_exit_mut(deps.storage).save(&EmergencyExit {
+            height: env.block.height,
+            time: env.block.time,
+            aterra_amount,
+            stable_amount: Uint128::zero(),
+        })?;
+        return Ok(Response::new().add_attribute("stable_amount", Uint128::zero()));
+    }
+
+    let contract_balance =
+        query_native_balance(deps.as_ref(), env.contract.address, config.stable_denom)?;
+
+    // Save data for reply handler
+    pending_emergency_exit_mut(deps.storage).save(&PendingEmergencyExit {
+        prev_stable_balance: contract_balance,
+        aterra_amount,
+    })?;
+
+    let anchor_redeem_res = strategy.redeem_stable(aterra_amount, EMERGENCY_EXIT_REPLY_ID)?;
+    Ok(Response::new()
+        .add_submessages(anchor_redeem_res.messages)
+        .add_attributes(anchor_redeem_res.attributes))
+}
+
+pub fn handle_reply_emergency_exit(
+    deps: DepsMut,
+    env: Env,
+    result: ContractResult<SubMsgExecutionResponse>,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    // Retrieve & clear saved data
+    let mut pending_emergency_exit = pending_emergency_exit_mut(deps.storage);
+    let PendingEmergencyExit {
+        prev_stable_balance,
+        aterra_amount,
+    } = pending_emergency_exit.load()?;
+    pending_emergency_exit.remove();
+
+    match result {
+        ContractResult::Ok(..) => {
+            let new_stable_balance =
+                query_native_balance(deps.as_ref(), env.contract.address, config.stable_denom)?;
+
+            // Difference is the stable amount redeemed from Anchor
+            let stable_amount = new_stable_balance - prev_stable_balance;
+
+            emergency_exit_mut(deps.storage).save(&EmergencyExit {
+                height: env.block.height,
+                time: env.block.time,
+                aterra_amount,
+                stable_amount,
+            })?;
+
+            Ok(Response::new().add_attribute("stable_amount", stable_amount))
+        }
+        ContractResult::Err(e) => Err(ContractError::Std(StdError::generic_err(e))),
+    }
+}
+
+/// Burns burn_amount aliceUST from sender and sends recipient its share of the UST held
+pub fn redeem_pro_rata(
+    mut deps: DepsMut,
+    env: Env,
+    config: &Config,
+    sender: &Addr,
+    recipient: &Addr,
+    burn_amount: Uint128,
+) -> Result<Response, ContractError> {
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+    let contract_balance = query_native_balance(
+        deps.as_ref(),
+        env.contract.address.clone(),
+        config.stable_denom.clone(),
+    )?;
+    let stable_amount = contract_balance.multiply_ratio(burn_amount, total_supply);
+
+    execute_burn(
+        deps.branch(),
+        env,
+        MessageInfo {
+            sender: sender.clone(),
+            funds: vec![],
+        },
+        burn_amount,
+    )?;
+    record_withdrawal(deps.storage, sender, stable_amount)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![deduct_tax(
+                deps.as_ref(),
+                Coin {
+                    denom: config.stable_denom.clone(),
+                    amount: stable_amount,
+                },
+            )?],
+        }))
+        .add_attribute("stable_amount", stable_amount))
+}
@@ -0,0 +1,112 @@
+//! Holder count of aliceUST accounts, kept up to date by the balance operations and
+//! recounted by keepers after migrating from a version without it
+
+use cosmwasm_std::{
+    Addr, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Storage,
+};
+use cw20_base::state::BALANCES;
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::state::{holder_count_mut, holder_count_read, holder_recount_mut, holder_recount_read};
+
+const MAX_RECOUNT_LIMIT: u32 = 100;
+const DEFAULT_RECOUNT_LIMIT: u32 = 30;
+
+fn is_holder(storage: &dyn Storage, address: &Addr) -> StdResult<bool> {
+    Ok(!BALANCES
+        .may_load(storage, address)?
+        .unwrap_or_default()
+        .is_zero())
+}
+
+/// Returns whether addresses hold aliceUST before their balances change
+pub fn holders_before_change(storage: &dyn Storage, addresses: &[&Addr]) -> StdResult<Vec<bool>> {
+    addresses
+        .iter()
+        .map(|address| is_holder(storage, address))
+        .collect()
+}
+
+/// Updates the holder count from whether addresses held aliceUST before the change.
+/// During a holder recount, only addresses already counted update the recount
+pub fn update_holder_count(
+    storage: &mut dyn Storage,
+    addresses: &[&Addr],
+    were_holders: Vec<bool>,
+) -> StdResult<()> {
+    let recount = holder_recount_read(storage).may_load()?;
+    let mut holder_count = match &recount {
+        Some(recount) => recount.holder_count,
+        None => holder_count_read(storage).may_load()?.unwrap_or_default(),
+    };
+    for (address, was_holder) in addresses.iter().zip(were_holders) {
+        if let Some(recount) = &recount {
+            let counted = match &recount.start_after {
+                Some(start_after) => address.as_str() <= start_after.as_str(),
+                None => false,
+            };
+            if !counted {
+                continue;
+            }
+        }
+        match (was_holder, is_holder(storage, address)?) {
+            (false, true) => holder_count += 1,
+            (true, false) => holder_count -= 1,
+            _ => {}
+        }
+    }
+    match recount {
+        Some(mut recount) => {
+            recount.holder_count = holder_count;
+            holder_recount_mut(storage).save(&recount)
+        }
+        None => holder_count_mut(storage).save(&holder_count),
+    }
+}
+
+/// Counts the holders among the next limit balances of the holder recount,
+/// saves the holder count once all balances are counted. Executable by anyone
+pub fn execute_recount_holders(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut recount = holder_recount_read(deps.storage)
+        .may_load()?
+        .ok_or(ContractError::NoHolderRecount {})?;
+
+    let limit = limit
+        .unwrap_or(DEFAULT_RECOUNT_LIMIT)
+        .min(MAX_RECOUNT_LIMIT) as usize;
+    let start = recount
+        .start_after
+        .as_ref()
+        .map(|address| Bound::exclusive(address.as_bytes()));
+    let balances = BALANCES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (_, balance) in balances.iter() {
+        if !balance.is_zero() {
+            recount.holder_count += 1;
+        }
+    }
+
+    let done = balances.len() < limit;
+    if done {
+        holder_count_mut(deps.storage).save(&recount.holder_count)?;
+        holder_recount_mut(deps.storage).remove();
+    } else if let Some((address, _)) = balances.last() {
+        let address = String::from_utf8(address.clone()).map_err(StdError::from)?;
+        recount.start_after = Some(Addr::unchecked(address));
+        holder_recount_mut(deps.storage).save(&recount)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("counted_balances", balances.len().to_string())
+        .add_attribute("holder_count", recount.holder_count.to_string())
+        .add_attribute("done", done.to_string()))
+}
@@ -15,6 +15,78 @@ pub enum ContractError {
     #[error("Proto encoding error")]
     ProtoEncodeError {},
 
+    #[error("Swapped stable amount is below the minimum")]
+    SwapMinStableAmount {},
+
+    #[error("Swapped target amount is below the minimum")]
+    SwapMinTargetAmount {},
+
+    #[error("Target denom is not supported")]
+    UnsupportedTargetDenom {},
+
+    #[error("Total deposit cap reached")]
+    TotalDepositCapReached {},
+
+    #[error("Deposit cap reached for address")]
+    AddressDepositCapReached {},
+
+    #[error("Withdrawal queue is empty")]
+    WithdrawalQueueEmpty {},
+
+    #[error("Withdrawal ticket not found")]
+    WithdrawalTicketNotFound {},
+
+    #[error("Schedule not found")]
+    ScheduleNotFound {},
+
+    #[error("Schedule interval too short")]
+    ScheduleIntervalTooShort {},
+
+    #[error("Schedule amount too low")]
+    ScheduleAmountTooLow {},
+
+    #[error("Too many schedules")]
+    TooManySchedules {},
+
+    #[error("No schedules are due")]
+    NoDueSchedules {},
+
+    #[error("Cannot refer own account")]
+    InvalidReferrer {},
+
+    #[error("Exchange rate was recorded recently")]
+    RateRecordedRecently {},
+
+    #[error("No holder recount in progress")]
+    NoHolderRecount {},
+
+    #[error("aliceUST supply exceeds aUST held")]
+    Undercollateralized {},
+
+    #[error("No surplus aUST to skim")]
+    NoSurplus {},
+
+    #[error("Asset backs aliceUST or in-flight operations")]
+    ProtectedAsset {},
+
+    #[error("Paused")]
+    Paused {},
+
+    #[error("Contract exited Anchor")]
+    EmergencyExited {},
+
+    #[error("No pending owner")]
+    NoPendingOwner {},
+
+    #[error("Operator daily limit reached")]
+    OperatorDailyLimitReached {},
+
+    #[error("SendAuthorization spend limit too low")]
+    InsufficientSendAuthorization {},
+
+    #[error("Deposit batch is empty")]
+    EmptyDepositBatch {},
+
     #[error("{0}")]
     Payment(#[from] cw0::PaymentError),
 
@@ -39,6 +111,15 @@ pub enum ContractError {
 
     #[error("Minting cannot exceed the cap")]
     CannotExceedCap {},
+
+    #[error("Logo binary data exceeds 5KB limit")]
+    LogoTooBig {},
+
+    #[error("Invalid xml preamble for SVG")]
+    InvalidXmlPreamble {},
+
+    #[error("Invalid png header")]
+    InvalidPngHeader {},
 }
 
 // Reference: cw20-staking
@@ -54,12 +135,9 @@ impl From<cw20_base::ContractError> for ContractError {
             cw20_base::ContractError::Expired {} => ContractError::Expired {},
             cw20_base::ContractError::NoAllowance {} => ContractError::NoAllowance {},
             cw20_base::ContractError::CannotExceedCap {} => ContractError::CannotExceedCap {},
-            // This should never happen, as this contract doesn't use logo
-            cw20_base::ContractError::LogoTooBig {}
-            | cw20_base::ContractError::InvalidPngHeader {}
-            | cw20_base::ContractError::InvalidXmlPreamble {} => {
-                ContractError::Std(StdError::generic_err(err.to_string()))
-            }
+            cw20_base::ContractError::LogoTooBig {} => ContractError::LogoTooBig {},
+            cw20_base::ContractError::InvalidXmlPreamble {} => ContractError::InvalidXmlPreamble {},
+            cw20_base::ContractError::InvalidPngHeader {} => ContractError::InvalidPngHeader {},
         }
     }
 }
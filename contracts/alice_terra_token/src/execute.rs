@@ -1,26 +1,66 @@
-use cosmwasm_bignumber::Uint256;
+use cosmwasm_bignumber::{Decimal256, Uint256};
 use cosmwasm_std::{
-    coin, BankMsg, Coin, ContractResult, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError,
-    SubMsgExecutionResponse, Uint128,
+    coin, Addr, BankMsg, Coin, ContractResult, CosmosMsg, Deps, DepsMut, Env, Event, MessageInfo,
+    Response, StdError, StdResult, SubMsgExecutionResponse, Uint128,
 };
-use cw0::{may_pay, must_pay};
+use cw0::{may_pay, one_coin, PaymentError};
 use cw20::BalanceResponse;
-use cw20_base::contract::{execute_burn, execute_mint, execute_transfer, query_balance};
+use cw20_base::contract::query_balance;
+use cw20_base::state::{BALANCES, TOKEN_INFO};
 
-use crate::anchor::{anchor_deposit_stable, anchor_redeem_stable, query_cw20_balance};
+use crate::authz::{query_send_authorization_limit, MSG_SEND_TYPE_URL};
+use crate::earnings::{record_deposit, record_withdrawal};
+use crate::emergency_exit::{assert_not_exited, is_exited, redeem_pro_rata};
 use crate::error::ContractError;
+use crate::fees::{
+    is_fee_exempt, mint_fee, redeem_fee_ratio, transfer_fee, validate_fee_recipients,
+};
+use crate::msg::{AuthorizedDepositMsg, DepositCapsMsg, FeeRecipientMsg};
+use crate::operators::use_operator_limit;
+use crate::terra_market::terra_market_swap;
 
 use crate::query::query_native_balance;
+use crate::rate_history::try_record_rate;
+use crate::referral::record_referral;
+use crate::snapshot::{execute_burn, execute_mint};
 use crate::state::{
-    config_read, pending_deposit_stable_mut, pending_redeem_stable_mut, Config,
-    PendingDepositStable, PendingRedeemStable,
+    config_read, pending_deposit_stable_mut, pending_redeem_stable_mut, pending_swap_deposit_mut,
+    pending_swap_redeem_mut, save_config, Config, DepositShare, FeeRecipient, PendingDepositStable,
+    PendingRedeemStable, PendingSwapDeposit, PendingSwapRedeem, RedeemFeeTier, TaxPolicy,
 };
 use crate::utils::{compute_tax, deduct_tax, proto_encode};
+use crate::withdrawal_queue::enqueue_withdrawal;
+use crate::yield_strategy::yield_strategy;
 use cosmos_sdk_proto::cosmos::authz::v1beta1::MsgExec;
 use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
 
 pub const DEPOSIT_STABLE_REPLY_ID: u64 = 1;
 pub const REDEEM_STABLE_REPLY_ID: u64 = 2;
+pub const PROCESS_WITHDRAWALS_REPLY_ID: u64 = 3;
+pub const SWAP_DEPOSIT_REPLY_ID: u64 = 4;
+pub const SWAP_REDEEM_REPLY_ID: u64 = 5;
+pub const EMERGENCY_EXIT_REPLY_ID: u64 = 6;
+
+/// Config fields to update, see `ExecuteMsg::UpdateConfig`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfigUpdate {
+    pub redeem_fee_ratio: Option<Decimal256>,
+    pub redeem_fee_tiers: Option<Vec<RedeemFeeTier>>,
+    pub deposit_fee_ratio: Option<Decimal256>,
+    pub referral_share: Option<Decimal256>,
+    pub fee_recipients: Option<Vec<FeeRecipientMsg>>,
+    pub deposit_caps: Option<DepositCapsMsg>,
+    pub swap_denoms: Option<Vec<String>>,
+    pub tax_policy: Option<TaxPolicy>,
+}
+
+/// Optional redeem behaviour, see `ExecuteMsg::RedeemStable`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RedeemOptions {
+    pub queue_if_illiquid: bool,
+    pub target_denom: Option<String>,
+    pub min_target_amount: Option<Uint128>,
+}
 
 pub fn execute_deposit_stable_authorized(
     mut deps: DepsMut,
@@ -28,15 +68,84 @@ pub fn execute_deposit_stable_authorized(
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
+    referrer: Option<String>,
 ) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+
     let config = config_read(deps.storage).load()?;
+    use_operator_limit(deps.storage, &env, &config, &info.sender, amount)?;
+    // Sender is the owner or an operator, trusted to set the recipient's referrer
+    record_referral(deps.branch(), &recipient, referrer)?;
 
-    if info.sender != config.owner {
+    deposit_stable_authorized(
+        deps,
+        env,
+        info,
+        recipient.clone(),
+        vec![DepositShare {
+            recipient,
+            stable_amount: amount,
+        }],
+    )
+}
+
+pub fn execute_deposit_stable_authorized_batch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    granter: String,
+    deposits: Vec<AuthorizedDepositMsg>,
+) -> Result<Response, ContractError> {
+    // Only the granter chooses the recipients of its funds, directly or by relay
+    let granter = deps.api.addr_validate(&granter)?;
+    if info.sender != granter {
         return Err(ContractError::Unauthorized {});
     }
+    if deposits.is_empty() {
+        return Err(ContractError::EmptyDepositBatch {});
+    }
+    let shares = deposits
+        .into_iter()
+        .map(|deposit| {
+            Ok(DepositShare {
+                recipient: deps.api.addr_validate(&deposit.recipient)?,
+                stable_amount: deposit.amount,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    deposit_stable_authorized(deps, env, info, granter, shares)
+}
+
+/// Uses granter's SendAuthorization to retrieve the total stablecoin amount of shares,
+/// and deposits it for the share recipients
+fn deposit_stable_authorized(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    granter: Addr,
+    shares: Vec<DepositShare>,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+    if shares.iter().any(|share| share.stable_amount.is_zero()) {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    let amount: Uint128 = shares.iter().map(|share| share.stable_amount).sum();
 
-    // use received funds for 2*tax:
-    // 1. MsgExec(MsgSend) from user to contract
+    // Fail early instead of relying on the chain to reject MsgExec(MsgSend)
+    let spend_limit = query_send_authorization_limit(
+        deps.as_ref(),
+        &env,
+        granter.to_string(),
+        env.contract.address.to_string(),
+        &config.stable_denom,
+    )?;
+    if spend_limit < amount {
+        return Err(ContractError::InsufficientSendAuthorization {});
+    }
+
+    // use received funds for 2*tax on the total amount:
+    // 1. MsgExec(MsgSend) from granter to contract
     // 2. Anchor deposit
     let received_stable_amount = may_pay(&info, config.stable_denom.as_str())?;
     let stable_coin = coin(amount.u128(), config.stable_denom.clone());
@@ -45,37 +154,47 @@ pub fn execute_deposit_stable_authorized(
         return Err(ContractError::TaxFundsTooLow {});
     }
 
+    let msg_exec = authorized_send_msg(&env, &config.stable_denom, &[(granter, amount)])?;
+    let deposit_res = deposit_stable_shares(deps, env, shares, stable_coin)?;
+
+    Ok(Response::new()
+        .add_message(msg_exec)
+        .add_attributes(deposit_res.attributes)
+        .add_submessages(deposit_res.messages))
+}
+
+/// MsgExec of a MsgSend to the contract from each granter, using their SendAuthorization
+pub fn authorized_send_msg(
+    env: &Env,
+    denom: &str,
+    sends: &[(Addr, Uint128)],
+) -> Result<CosmosMsg, ContractError> {
     // MsgExec is not a supported CosmosMsg, so we construct a MsgExec(MsgSend) proto manually
+    let msgs = sends
+        .iter()
+        .map(|(granter, amount)| {
+            Ok(prost_types::Any {
+                type_url: MSG_SEND_TYPE_URL.to_string(),
+                value: proto_encode(MsgSend {
+                    from_address: granter.to_string(),
+                    to_address: env.contract.address.to_string(),
+                    amount: vec![cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+                        denom: denom.to_string(),
+                        amount: amount.to_string(),
+                    }],
+                })?,
+            })
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
     let msg_exec = proto_encode(MsgExec {
         grantee: env.contract.address.to_string(),
-        msgs: vec![prost_types::Any {
-            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
-            value: proto_encode(MsgSend {
-                from_address: recipient.clone(),
-                to_address: env.contract.address.to_string(),
-                amount: vec![cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
-                    denom: config.stable_denom,
-                    amount: amount.to_string(),
-                }],
-            })?,
-        }],
+        msgs,
     })?;
 
-    let deposit_res = deposit_stable(
-        deps.branch(),
-        env,
-        info,
-        Some(recipient),
-        stable_coin.amount,
-    )?;
-
-    Ok(Response::new()
-        .add_message(CosmosMsg::Stargate {
-            type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
-            value: msg_exec.into(),
-        })
-        .add_attributes(deposit_res.attributes)
-        .add_submessages(deposit_res.messages))
+    Ok(CosmosMsg::Stargate {
+        type_url: "/cosmos.authz.v1beta1.MsgExec".to_string(),
+        value: msg_exec.into(),
+    })
 }
 
 pub fn execute_deposit_stable(
@@ -83,52 +202,211 @@ pub fn execute_deposit_stable(
     env: Env,
     info: MessageInfo,
     recipient: Option<String>,
+    min_stable_amount: Option<Uint128>,
+    referrer: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = config_read(deps.storage).load()?;
 
-    // Only accept stable denom coins
-    let received_stable_amount = must_pay(&info, config.stable_denom.as_str())?;
+    // Only accept stable denom or swap denom coins
+    let received_coin = one_coin(&info)?;
+    let recipient = deps
+        .api
+        .addr_validate(&recipient.unwrap_or_else(|| info.sender.to_string()))?;
+    // Ignore referrers set for other recipients, so others can't squat them
+    if info.sender == recipient {
+        record_referral(deps.branch(), &recipient, referrer)?;
+    }
+
+    if received_coin.denom == config.stable_denom {
+        // Deduct tax for Anchor deposit operation
+        let stable_amount = deduct_tax(deps.as_ref(), received_coin.clone())?.amount;
+
+        return deposit_stable(deps.branch(), env, recipient, received_coin, stable_amount);
+    }
 
-    // Deduct tax for Anchor deposit operation
-    let stable_amount = deduct_tax(
+    if !config.swap_denoms.contains(&received_coin.denom) {
+        return Err(PaymentError::MissingDenom(config.stable_denom).into());
+    }
+
+    let stable_balance = query_native_balance(
         deps.as_ref(),
-        coin(received_stable_amount.u128(), config.stable_denom),
-    )?
-    .amount;
+        env.contract.address.clone(),
+        config.stable_denom.clone(),
+    )?;
+
+    // Save data for reply handler
+    pending_swap_deposit_mut(deps.storage).save(&PendingSwapDeposit {
+        prev_stable_balance: stable_balance,
+        recipient,
+        offer_coin: received_coin.clone(),
+        min_stable_amount,
+    })?;
 
-    deposit_stable(deps.branch(), env, info, recipient, stable_amount)
+    // Swap to stable denom first, Anchor deposit happens in the reply handler
+    terra_market_swap(
+        &env,
+        received_coin,
+        config.stable_denom,
+        SWAP_DEPOSIT_REPLY_ID,
+    )
+}
+
+pub fn handle_reply_swap_deposit(
+    deps: DepsMut,
+    env: Env,
+    result: ContractResult<SubMsgExecutionResponse>,
+) -> Result<Response, ContractError> {
+    let config: Config = config_read(deps.storage).load()?;
+
+    // Retrieve & clear saved data
+    let mut pending_swap_deposit = pending_swap_deposit_mut(deps.storage);
+    let PendingSwapDeposit {
+        prev_stable_balance,
+        recipient,
+        offer_coin,
+        min_stable_amount,
+    } = pending_swap_deposit.load()?;
+    pending_swap_deposit.remove();
+
+    match result {
+        ContractResult::Ok(..) => {
+            let new_stable_balance = query_native_balance(
+                deps.as_ref(),
+                env.contract.address.clone(),
+                config.stable_denom.clone(),
+            )?;
+
+            // Difference is the stable amount returned by the swap
+            let swapped_amount = new_stable_balance - prev_stable_balance;
+            if swapped_amount < min_stable_amount.unwrap_or_default() {
+                return Err(ContractError::SwapMinStableAmount {});
+            }
+
+            // Deduct tax for Anchor deposit operation
+            let stable_amount = deduct_tax(
+                deps.as_ref(),
+                coin(swapped_amount.u128(), config.stable_denom),
+            )?
+            .amount;
+
+            Ok(
+                deposit_stable(deps, env, recipient, offer_coin, stable_amount)?
+                    .add_attribute("swapped_amount", swapped_amount),
+            )
+        }
+        ContractResult::Err(e) => Err(ContractError::Std(StdError::generic_err(e))),
+    }
+}
+
+/// Errors if the aliceUST expected to be minted for shares at the current exchange rate
+/// would exceed the total cap or a recipient's address cap
+pub fn check_deposit_caps(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    shares: &[DepositShare],
+) -> Result<(), ContractError> {
+    if config.total_deposit_cap.is_none() && config.address_deposit_cap.is_none() {
+        return Ok(());
+    }
+
+    let exchange_rate = yield_strategy(config).query_exchange_rate(deps, env.block.height)?;
+    let expected_mint = |stable_amount: Uint128| -> Uint128 {
+        (Uint256::from(stable_amount) / exchange_rate).into()
+    };
+
+    if let Some(total_cap) = config.total_deposit_cap {
+        let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+        let total_mint: Uint128 = shares
+            .iter()
+            .map(|share| expected_mint(share.stable_amount))
+            .sum();
+        if total_supply + total_mint > total_cap {
+            return Err(ContractError::TotalDepositCapReached {});
+        }
+    }
+
+    // Soft limit, holders can transfer out & deposit again
+    if let Some(address_cap) = config.address_deposit_cap {
+        for share in shares {
+            let balance = BALANCES
+                .may_load(deps.storage, &share.recipient)?
+                .unwrap_or_default();
+            let recipient_mint: Uint128 = shares
+                .iter()
+                .filter(|other| other.recipient == share.recipient)
+                .map(|other| expected_mint(other.stable_amount))
+                .sum();
+            if balance + recipient_mint > address_cap {
+                return Err(ContractError::AddressDepositCapReached {});
+            }
+        }
+    }
+
+    Ok(())
 }
 
 pub fn deposit_stable(
     deps: DepsMut,
     env: Env,
-    info: MessageInfo,
-    recipient: Option<String>,
+    recipient: Addr,
+    deposit_coin: Coin,
     stable_amount: Uint128,
+) -> Result<Response, ContractError> {
+    deposit_stable_shares(
+        deps,
+        env,
+        vec![DepositShare {
+            recipient,
+            stable_amount,
+        }],
+        deposit_coin,
+    )
+}
+
+/// Deposits the total stable amount of shares into Anchor in a single deposit
+pub fn deposit_stable_shares(
+    mut deps: DepsMut,
+    env: Env,
+    shares: Vec<DepositShare>,
+    deposit_coin: Coin,
 ) -> Result<Response, ContractError> {
     let config: Config = config_read(deps.storage).load()?;
 
-    let aterra_balance = query_cw20_balance(
-        deps.as_ref(),
-        config.aterra_token_addr,
-        env.contract.address,
-    )?;
+    assert_not_exited(deps.storage)?;
+
+    // Fail early, the exact total cap is enforced when minting
+    check_deposit_caps(deps.as_ref(), &env, &config, &shares)?;
+    let stable_amount: Uint128 = shares.iter().map(|share| share.stable_amount).sum();
+
+    try_record_rate(deps.branch(), &env)?;
+
+    let strategy = yield_strategy(&config);
+    let aterra_balance = strategy.query_position(deps.as_ref(), env.contract.address)?;
 
     // Save data for reply handler
-    let recipient = recipient.unwrap_or_else(|| info.sender.to_string());
     pending_deposit_stable_mut(deps.storage).save(&PendingDepositStable {
         prev_aterra_balance: aterra_balance,
-        recipient: deps.api.addr_validate(recipient.as_str())?,
-        stable_amount,
+        shares,
+        deposit_coin,
     })?;
 
-    let anchor_deposit_res = anchor_deposit_stable(deps, stable_amount, DEPOSIT_STABLE_REPLY_ID)?;
+    let anchor_deposit_res = strategy.deposit_stable(stable_amount, DEPOSIT_STABLE_REPLY_ID)?;
     Ok(Response::new()
         .add_attributes(anchor_deposit_res.attributes)
         .add_submessages(anchor_deposit_res.messages)
         .add_attribute("stable_amount", stable_amount))
 }
 
+/// aliceUST total supply must stay backed 1:1 by the aUST held
+fn assert_backed(deps: Deps, aterra_balance: Uint128) -> Result<(), ContractError> {
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+    if total_supply > aterra_balance {
+        return Err(ContractError::Undercollateralized {});
+    }
+    Ok(())
+}
+
 pub fn handle_reply_deposit_stable(
     mut deps: DepsMut,
     env: Env,
@@ -140,87 +418,288 @@ pub fn handle_reply_deposit_stable(
     let mut pending_deposit_stable = pending_deposit_stable_mut(deps.storage);
     let PendingDepositStable {
         prev_aterra_balance,
-        recipient,
-        stable_amount: _stable_amount,
+        shares,
+        deposit_coin,
     } = pending_deposit_stable.load()?;
     pending_deposit_stable.remove();
 
     match result {
         ContractResult::Ok(..) => {
-            let new_aterra_balance = query_cw20_balance(
-                deps.as_ref(),
-                config.aterra_token_addr,
-                env.contract.address.clone(),
-            )?;
+            let new_aterra_balance = yield_strategy(&config)
+                .query_position(deps.as_ref(), env.contract.address.clone())?;
 
             // Difference is the aUST minted in Anchor
             let mint_amount = new_aterra_balance - prev_aterra_balance;
 
-            // call execute_mint as contract self (no one else has permission)
-            execute_mint(
-                deps.branch(),
-                env.clone(),
-                MessageInfo {
-                    sender: env.contract.address,
-                    funds: vec![],
-                },
-                recipient.to_string(),
-                mint_amount,
-            )?;
+            // Split by stable amount, the last recipient receives the rounding remainder
+            let total_stable_amount: Uint128 = shares.iter().map(|share| share.stable_amount).sum();
+            let mut remaining_amount = mint_amount;
+            let mut total_fee_amount = Uint128::zero();
+            let mut recipient_amounts = vec![];
+            for (i, share) in shares.iter().enumerate() {
+                let share_mint_amount = if i == shares.len() - 1 {
+                    remaining_amount
+                } else {
+                    mint_amount.multiply_ratio(share.stable_amount, total_stable_amount)
+                };
+                remaining_amount -= share_mint_amount;
+
+                // Deposit fee is minted to the fee recipients
+                let fee_amount = if is_fee_exempt(deps.storage, &config, &share.recipient)? {
+                    Uint128::zero()
+                } else {
+                    Uint128::from(config.deposit_fee_ratio * Uint256::from(share_mint_amount))
+                };
+                total_fee_amount += fee_amount;
+                recipient_amounts.push(share_mint_amount - fee_amount);
+            }
+
+            // Deposit fees are not counted against the total cap
+            if let Some(total_cap) = config.total_deposit_cap {
+                let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+                if total_supply + (mint_amount - total_fee_amount) > total_cap {
+                    return Err(ContractError::TotalDepositCapReached {});
+                }
+            }
+
+            for (share, amount) in shares.iter().zip(recipient_amounts) {
+                // call execute_mint as contract self (no one else has permission)
+                execute_mint(
+                    deps.branch(),
+                    env.clone(),
+                    MessageInfo {
+                        sender: env.contract.address.clone(),
+                        funds: vec![],
+                    },
+                    share.recipient.to_string(),
+                    amount,
+                )?;
+
+                record_deposit(deps.storage, &share.recipient, share.stable_amount)?;
+            }
+            if total_fee_amount > Uint128::zero() {
+                mint_fee(deps.branch(), env, &config, total_fee_amount)?;
+            }
+            assert_backed(deps.as_ref(), new_aterra_balance)?;
 
-            Ok(Response::new().add_attribute("mint_amount", mint_amount))
+            Ok(Response::new()
+                .add_attribute("mint_amount", mint_amount)
+                .add_attribute("deposit_fee_amount", total_fee_amount)
+                .add_attribute("deposit_amount", deposit_coin.amount)
+                .add_attribute("deposit_denom", deposit_coin.denom))
         }
         ContractResult::Err(e) => Err(ContractError::Std(StdError::generic_err(e))),
     }
 }
 
+fn cap_attribute(cap: Option<Uint128>) -> String {
+    cap.map_or_else(|| "none".to_string(), |cap| cap.to_string())
+}
+
+fn fee_recipients_attribute(fee_recipients: &[FeeRecipient]) -> String {
+    fee_recipients
+        .iter()
+        .map(|fee_recipient| format!("{}:{}", fee_recipient.address, fee_recipient.share))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Updates low-risk config fields, unset fields are unchanged.
+/// Anchor addresses & roles are only changed by migration
+pub fn execute_update_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    let mut config = config_read(deps.storage).load()?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut event = Event::new("update_config");
+
+    if let Some(redeem_fee_ratio) = update.redeem_fee_ratio {
+        event = event
+            .add_attribute("old_redeem_fee_ratio", config.redeem_fee_ratio.to_string())
+            .add_attribute("new_redeem_fee_ratio", redeem_fee_ratio.to_string());
+        config.redeem_fee_ratio = redeem_fee_ratio;
+    }
+
+    if let Some(redeem_fee_tiers) = update.redeem_fee_tiers {
+        event = event
+            .add_attribute(
+                "old_redeem_fee_tiers",
+                format!("{:?}", config.redeem_fee_tiers),
+            )
+            .add_attribute("new_redeem_fee_tiers", format!("{:?}", redeem_fee_tiers));
+        config.redeem_fee_tiers = redeem_fee_tiers;
+    }
+
+    if let Some(deposit_fee_ratio) = update.deposit_fee_ratio {
+        event = event
+            .add_attribute(
+                "old_deposit_fee_ratio",
+                config.deposit_fee_ratio.to_string(),
+            )
+            .add_attribute("new_deposit_fee_ratio", deposit_fee_ratio.to_string());
+        config.deposit_fee_ratio = deposit_fee_ratio;
+    }
+
+    if let Some(referral_share) = update.referral_share {
+        event = event
+            .add_attribute("old_referral_share", config.referral_share.to_string())
+            .add_attribute("new_referral_share", referral_share.to_string());
+        config.referral_share = referral_share;
+    }
+
+    if let Some(fee_recipients) = update.fee_recipients {
+        let fee_recipients = validate_fee_recipients(deps.api, fee_recipients)?;
+        event = event
+            .add_attribute(
+                "old_fee_recipients",
+                fee_recipients_attribute(&config.fee_recipients),
+            )
+            .add_attribute(
+                "new_fee_recipients",
+                fee_recipients_attribute(&fee_recipients),
+            );
+        config.fee_recipients = fee_recipients;
+    }
+
+    if let Some(deposit_caps) = update.deposit_caps {
+        event = event
+            .add_attribute(
+                "old_total_deposit_cap",
+                cap_attribute(config.total_deposit_cap),
+            )
+            .add_attribute(
+                "new_total_deposit_cap",
+                cap_attribute(deposit_caps.total_cap),
+            )
+            .add_attribute(
+                "old_address_deposit_cap",
+                cap_attribute(config.address_deposit_cap),
+            )
+            .add_attribute(
+                "new_address_deposit_cap",
+                cap_attribute(deposit_caps.address_cap),
+            );
+        config.total_deposit_cap = deposit_caps.total_cap;
+        config.address_deposit_cap = deposit_caps.address_cap;
+    }
+
+    if let Some(swap_denoms) = update.swap_denoms {
+        event = event
+            .add_attribute("old_swap_denoms", config.swap_denoms.join(","))
+            .add_attribute("new_swap_denoms", swap_denoms.join(","));
+        config.swap_denoms = swap_denoms;
+    }
+
+    if let Some(tax_policy) = update.tax_policy {
+        event = event
+            .add_attribute("old_tax_policy", format!("{:?}", config.tax_policy))
+            .add_attribute("new_tax_policy", format!("{:?}", tax_policy));
+        config.tax_policy = tax_policy;
+    }
+
+    save_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_event(event))
+}
+
 pub fn execute_redeem_stable(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     burn_amount: Uint128,
     recipient: Option<String>,
+    options: RedeemOptions,
 ) -> Result<Response, ContractError> {
     let config: Config = config_read(deps.storage).load()?;
 
+    // Target denom other than stable denom must be swappable
+    let target_denom = options
+        .target_denom
+        .filter(|target_denom| *target_denom != config.stable_denom);
+    if let Some(target_denom) = &target_denom {
+        if !config.swap_denoms.contains(target_denom) {
+            return Err(ContractError::UnsupportedTargetDenom {});
+        }
+    }
+
+    // After the emergency exit, the UST held is paid out without any swap
+    let exited = is_exited(deps.storage)?;
+    if exited && target_denom.is_some() {
+        return Err(ContractError::UnsupportedTargetDenom {});
+    }
+
     let BalanceResponse { balance } = query_balance(deps.as_ref(), info.sender.clone().into())?;
     if burn_amount > balance {
         return Err(ContractError::BalanceTooLow {});
     }
 
-    let recipient = recipient.unwrap_or_else(|| info.sender.to_string());
+    if !exited {
+        try_record_rate(deps.branch(), &env)?;
+    }
+
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
 
     // Collect redeem fee
-    let fee_amount = if recipient == config.owner {
+    let fee_amount = if is_fee_exempt(deps.storage, &config, &recipient)? {
         Uint128::zero()
     } else {
-        Uint128::from(config.redeem_fee_ratio * Uint256::from(burn_amount))
+        Uint128::from(redeem_fee_ratio(&config, burn_amount) * Uint256::from(burn_amount))
     };
     if fee_amount > Uint128::zero() {
-        execute_transfer(
+        transfer_fee(
             deps.branch(),
             env.clone(),
             info.clone(),
-            config.owner.to_string(),
+            &config,
             fee_amount,
         )?;
     }
     let final_burn_amount = burn_amount - fee_amount;
 
-    let contract_balance =
-        query_native_balance(deps.as_ref(), env.contract.address, config.stable_denom)?;
+    if exited {
+        return Ok(redeem_pro_rata(
+            deps,
+            env,
+            &config,
+            &info.sender,
+            &recipient,
+            final_burn_amount,
+        )?
+        .add_attribute("burn_amount", burn_amount)
+        .add_attribute("final_burn_amount", final_burn_amount)
+        .add_attribute("redeem_fee_amount", fee_amount));
+    }
+
+    let contract_balance = query_native_balance(
+        deps.as_ref(),
+        env.contract.address,
+        config.stable_denom.clone(),
+    )?;
 
     // Save data for reply handler
     pending_redeem_stable_mut(deps.storage).save(&PendingRedeemStable {
         prev_stable_balance: contract_balance,
         sender: info.sender,
-        recipient: deps.api.addr_validate(recipient.as_str())?,
+        recipient,
         burn_amount: final_burn_amount,
+        queue_if_illiquid: options.queue_if_illiquid,
+        target_denom,
+        min_target_amount: options.min_target_amount,
     })?;
 
     // Redeem stable submessage
     let anchor_redeem_res =
-        anchor_redeem_stable(deps.branch(), final_burn_amount, REDEEM_STABLE_REPLY_ID)?;
+        yield_strategy(&config).redeem_stable(final_burn_amount, REDEEM_STABLE_REPLY_ID)?;
     Ok(Response::new()
         .add_submessages(anchor_redeem_res.messages)
         .add_attributes(anchor_redeem_res.attributes)
@@ -243,6 +722,9 @@ pub fn handle_reply_redeem_stable(
         sender,
         recipient,
         burn_amount,
+        queue_if_illiquid,
+        target_denom,
+        min_target_amount,
     } = pending_redeem_stable.load()?;
     pending_redeem_stable.remove();
 
@@ -259,13 +741,39 @@ pub fn handle_reply_redeem_stable(
 
             execute_burn(
                 deps.branch(),
-                env,
+                env.clone(),
                 MessageInfo {
-                    sender,
+                    sender: sender.clone(),
                     funds: vec![],
                 },
                 burn_amount,
             )?;
+            record_withdrawal(deps.storage, &sender, stable_amount)?;
+
+            if let Some(target_denom) = target_denom {
+                let target_balance = query_native_balance(
+                    deps.as_ref(),
+                    env.contract.address.clone(),
+                    target_denom.clone(),
+                )?;
+
+                // Save data for reply handler
+                pending_swap_redeem_mut(deps.storage).save(&PendingSwapRedeem {
+                    prev_target_balance: target_balance,
+                    recipient,
+                    target_denom: target_denom.clone(),
+                    min_target_amount,
+                })?;
+
+                // Swap to target denom, recipient is paid in the reply handler
+                return Ok(terra_market_swap(
+                    &env,
+                    coin(stable_amount.u128(), config.stable_denom),
+                    target_denom,
+                    SWAP_REDEEM_REPLY_ID,
+                )?
+                .add_attribute("stable_amount", stable_amount));
+            }
 
             Ok(Response::new()
                 .add_message(CosmosMsg::Bank(BankMsg::Send {
@@ -280,6 +788,57 @@ pub fn handle_reply_redeem_stable(
                 }))
                 .add_attribute("stable_amount", stable_amount))
         }
+        ContractResult::Err(e)
+            if queue_if_illiquid && yield_strategy(&config).is_illiquid_error(&e) =>
+        {
+            // Anchor lacks liquidity, escrow aliceUST until liquidity returns
+            let ticket = enqueue_withdrawal(deps.branch(), env, sender, recipient, burn_amount)?;
+
+            Ok(Response::new()
+                .add_attribute("withdrawal_ticket_id", ticket.id.to_string())
+                .add_attribute("anchor_redeem_error", e))
+        }
+        ContractResult::Err(e) => Err(ContractError::Std(StdError::generic_err(e))),
+    }
+}
+
+pub fn handle_reply_swap_redeem(
+    deps: DepsMut,
+    env: Env,
+    result: ContractResult<SubMsgExecutionResponse>,
+) -> Result<Response, ContractError> {
+    // Retrieve & clear saved data
+    let mut pending_swap_redeem = pending_swap_redeem_mut(deps.storage);
+    let PendingSwapRedeem {
+        prev_target_balance,
+        recipient,
+        target_denom,
+        min_target_amount,
+    } = pending_swap_redeem.load()?;
+    pending_swap_redeem.remove();
+
+    match result {
+        ContractResult::Ok(..) => {
+            let new_target_balance =
+                query_native_balance(deps.as_ref(), env.contract.address, target_denom.clone())?;
+
+            // Difference is the target amount returned by the swap
+            let target_amount = new_target_balance - prev_target_balance;
+            if target_amount < min_target_amount.unwrap_or_default() {
+                return Err(ContractError::SwapMinTargetAmount {});
+            }
+
+            // Terra tax is charged in the target denom
+            Ok(Response::new()
+                .add_message(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: vec![deduct_tax(
+                        deps.as_ref(),
+                        coin(target_amount.u128(), target_denom),
+                    )?],
+                }))
+                .add_attribute("target_amount", target_amount))
+        }
         ContractResult::Err(e) => Err(ContractError::Std(StdError::generic_err(e))),
     }
 }
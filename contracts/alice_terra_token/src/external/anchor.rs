@@ -1,12 +1,16 @@
+use cosmwasm_bignumber::Decimal256;
 use cosmwasm_std::{
-    to_binary, Addr, Coin, CosmosMsg, Deps, DepsMut, QueryRequest, Response, StdResult, SubMsg,
-    Uint128, WasmMsg, WasmQuery,
+    to_binary, Addr, Coin, CosmosMsg, Deps, QueryRequest, Response, StdResult, SubMsg, Uint128,
+    WasmMsg, WasmQuery,
 };
 
-use crate::state::config_read;
+use crate::state::Config;
+use crate::yield_strategy::YieldStrategy;
 use cw20::Cw20ExecuteMsg;
 
-pub use crate::external::anchor_msg::{MarketCw20HookMsg, MarketExecuteMsg, MarketQueryMsg};
+pub use crate::external::anchor_msg::{
+    EpochStateResponse, MarketCw20HookMsg, MarketExecuteMsg, MarketQueryMsg,
+};
 
 pub fn query_cw20_balance(deps: Deps, cw20_addr: Addr, addr: Addr) -> StdResult<Uint128> {
     let balance_response = deps
@@ -20,68 +24,86 @@ pub fn query_cw20_balance(deps: Deps, cw20_addr: Addr, addr: Addr) -> StdResult<
     Ok(balance_response.balance)
 }
 
-/// Returns response with submessage to deposit stable_amount into Anchor.
-/// Warning: does not account for Terra tax.
-pub fn anchor_deposit_stable(
-    deps: DepsMut,
-    stable_amount: Uint128,
-    reply_id: u64,
-) -> StdResult<Response> {
-    // deposit `amount` uusd into Anchor by sending a `DepositStable`
-    // message to Money Market contract
+/// Anchor money market, positions are held in aTerra
+pub struct AnchorStrategy {
+    stable_denom: String,
+    money_market_addr: Addr,
+    aterra_token_addr: Addr,
+}
+
+impl AnchorStrategy {
+    pub fn new(config: &Config) -> Self {
+        AnchorStrategy {
+            stable_denom: config.stable_denom.clone(),
+            money_market_addr: config.money_market_addr.clone(),
+            aterra_token_addr: config.aterra_token_addr.clone(),
+        }
+    }
+}
 
-    // see https://github.com/Anchor-Protocol/anchor-earn/blob/master/src/fabricators/market-deposit-stable.ts
+impl YieldStrategy for AnchorStrategy {
+    fn deposit_stable(&self, stable_amount: Uint128, reply_id: u64) -> StdResult<Response> {
+        // deposit `amount` uusd into Anchor by sending a `DepositStable`
+        // message to Money Market contract
 
-    let config = config_read(deps.storage).load()?;
+        // see https://github.com/Anchor-Protocol/anchor-earn/blob/master/src/fabricators/market-deposit-stable.ts
 
-    let money_market_addr: String = config.money_market_addr.to_string();
+        Ok(Response::new()
+            .add_submessage(SubMsg::reply_always(
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: self.money_market_addr.to_string(),
+                    funds: vec![Coin {
+                        denom: self.stable_denom.clone(),
+                        amount: stable_amount,
+                    }],
+                    msg: to_binary(&MarketExecuteMsg::DepositStable {})?,
+                }),
+                reply_id,
+            ))
+            .add_attribute("anchor_deposit_amount", stable_amount))
+    }
 
-    Ok(Response::new()
-        .add_submessage(SubMsg::reply_always(
-            CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: money_market_addr,
-                funds: vec![Coin {
-                    denom: config.stable_denom,
-                    amount: stable_amount,
-                }],
-                msg: to_binary(&MarketExecuteMsg::DepositStable {})?,
-            }),
-            reply_id,
-        ))
-        .add_attribute("anchor_deposit_amount", stable_amount))
-}
+    fn redeem_stable(&self, position_amount: Uint128, reply_id: u64) -> StdResult<Response> {
+        // withdraw `amount` from Anchor by sending a `Send` message
+        // to the Anchor aTerra contract with the following format
+
+        // see https://github.com/Anchor-Protocol/anchor-earn/blob/master/src/fabricators/market-redeem-stable.ts
 
-/// Returns response with submessage to redeem aterra_amount from Anchor.
-pub fn anchor_redeem_stable(
-    deps: DepsMut,
-    aterra_amount: Uint128,
-    reply_id: u64,
-) -> StdResult<Response> {
-    // withdraw `amount` from Anchor by sending a `Send` message
-    // to the Anchor aTerra contract with the following format
+        Ok(Response::new()
+            .add_submessage(SubMsg::reply_always(
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: self.aterra_token_addr.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&Cw20ExecuteMsg::Send {
+                        contract: self.money_market_addr.to_string(),
+                        amount: position_amount,
+                        msg: to_binary(&MarketCw20HookMsg::RedeemStable {})?,
+                    })?,
+                }),
+                reply_id,
+            ))
+            .add_attribute("anchor_redeem_amount", position_amount))
+    }
 
-    // see https://github.com/Anchor-Protocol/anchor-earn/blob/master/src/fabricators/market-redeem-stable.ts
+    fn query_position(&self, deps: Deps, account: Addr) -> StdResult<Uint128> {
+        query_cw20_balance(deps, self.aterra_token_addr.clone(), account)
+    }
 
-    let (money_market_addr, aterra_token_addr) = {
-        let config = config_read(deps.storage).load()?;
-        (
-            config.money_market_addr.to_string(),
-            config.aterra_token_addr.to_string(),
-        )
-    };
+    /// Money market `NoStableAvailable` error, "Not enough uusd available; borrow demand too high"
+    fn is_illiquid_error(&self, error: &str) -> bool {
+        error.contains(&format!("Not enough {} available", self.stable_denom))
+    }
 
-    Ok(Response::new()
-        .add_submessage(SubMsg::reply_always(
-            CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: aterra_token_addr,
-                funds: vec![],
-                msg: to_binary(&Cw20ExecuteMsg::Send {
-                    contract: money_market_addr,
-                    amount: aterra_amount,
-                    msg: to_binary(&MarketCw20HookMsg::RedeemStable {})?,
+    /// Includes interest not yet distributed
+    fn query_exchange_rate(&self, deps: Deps, block_height: u64) -> StdResult<Decimal256> {
+        let epoch_state: EpochStateResponse =
+            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: self.money_market_addr.to_string(),
+                msg: to_binary(&MarketQueryMsg::EpochState {
+                    block_height: Some(block_height),
+                    distributed_interest: None,
                 })?,
-            }),
-            reply_id,
-        ))
-        .add_attribute("anchor_redeem_amount", aterra_amount))
+            }))?;
+        Ok(epoch_state.exchange_rate)
+    }
 }
@@ -0,0 +1,90 @@
+use cosmos_sdk_proto::cosmos::authz::v1beta1::{
+    GenericAuthorization, QueryGrantsRequest, QueryGrantsResponse,
+};
+use cosmos_sdk_proto::cosmos::bank::v1beta1::SendAuthorization;
+use cosmwasm_std::{
+    to_vec, ContractResult, Deps, Empty, Env, QueryRequest, StdError, SystemResult, Uint128,
+};
+use prost::Message;
+
+use crate::error::ContractError;
+use crate::utils::proto_encode;
+
+// For querying the Cosmos SDK authz module
+// Reference: https://github.com/cosmos/cosmos-sdk/blob/v0.44.0/proto/cosmos/authz/v1beta1/query.proto
+
+pub const GRANTS_QUERY_PATH: &str = "/cosmos.authz.v1beta1.Query/Grants";
+pub const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+pub const SEND_AUTHORIZATION_TYPE_URL: &str = "/cosmos.bank.v1beta1.SendAuthorization";
+pub const GENERIC_AUTHORIZATION_TYPE_URL: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
+
+/// Returns the amount of denom that grantee may send from granter through an unexpired
+/// SendAuthorization, zero if there is none. A GenericAuthorization for MsgSend is unlimited
+/// and returns Uint128::MAX
+pub fn query_send_authorization_limit(
+    deps: Deps,
+    env: &Env,
+    granter: String,
+    grantee: String,
+    denom: &str,
+) -> Result<Uint128, ContractError> {
+    let request: QueryRequest<Empty> = QueryRequest::Stargate {
+        path: GRANTS_QUERY_PATH.to_string(),
+        data: proto_encode(QueryGrantsRequest {
+            granter,
+            grantee,
+            msg_type_url: MSG_SEND_TYPE_URL.to_string(),
+            pagination: None,
+        })?
+        .into(),
+    };
+    let response = match deps.querier.raw_query(&to_vec(&request)?) {
+        SystemResult::Err(system_err) => {
+            return Err(
+                StdError::generic_err(format!("Querier system error: {}", system_err)).into(),
+            )
+        }
+        SystemResult::Ok(ContractResult::Err(err)) => {
+            return Err(StdError::generic_err(format!("Querier contract error: {}", err)).into())
+        }
+        SystemResult::Ok(ContractResult::Ok(response)) => response,
+    };
+    let response = QueryGrantsResponse::decode(response.as_slice())
+        .map_err(|_| StdError::parse_err("QueryGrantsResponse", "invalid protobuf"))?;
+
+    let mut limit = Uint128::zero();
+    for grant in response.grants {
+        let expired = matches!(
+            grant.expiration,
+            Some(expiration) if expiration.seconds <= env.block.time.seconds() as i64
+        );
+        let authorization = match grant.authorization {
+            Some(authorization) if !expired => authorization,
+            _ => continue,
+        };
+        if authorization.type_url == GENERIC_AUTHORIZATION_TYPE_URL {
+            let generic_authorization =
+                GenericAuthorization::decode(authorization.value.as_slice())
+                    .map_err(|_| StdError::parse_err("GenericAuthorization", "invalid protobuf"))?;
+            if generic_authorization.msg == MSG_SEND_TYPE_URL {
+                return Ok(Uint128::MAX);
+            }
+            continue;
+        }
+        if authorization.type_url != SEND_AUTHORIZATION_TYPE_URL {
+            continue;
+        }
+        let send_authorization = SendAuthorization::decode(authorization.value.as_slice())
+            .map_err(|_| StdError::parse_err("SendAuthorization", "invalid protobuf"))?;
+        for coin in send_authorization.spend_limit {
+            if coin.denom == denom {
+                let amount = coin
+                    .amount
+                    .parse::<u128>()
+                    .map_err(|_| StdError::parse_err("Coin", "invalid amount"))?;
+                limit = limit.max(Uint128::from(amount));
+            }
+        }
+    }
+    Ok(limit)
+}
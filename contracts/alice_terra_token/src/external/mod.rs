@@ -1,2 +1,5 @@
 pub mod anchor;
 mod anchor_msg;
+pub mod authz;
+pub mod terra_market;
+pub mod yield_strategy;
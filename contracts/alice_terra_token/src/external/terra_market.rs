@@ -0,0 +1,37 @@
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmos_sdk_proto::terra::market::v1beta1::MsgSwap;
+use cosmwasm_std::{Coin, CosmosMsg, Env, Response, SubMsg};
+
+use crate::error::ContractError;
+use crate::utils::proto_encode;
+
+/// Returns response with submessage to swap offer_coin into ask_denom through the Terra market module.
+/// Proceeds are sent to the contract itself.
+pub fn terra_market_swap(
+    env: &Env,
+    offer_coin: Coin,
+    ask_denom: String,
+    reply_id: u64,
+) -> Result<Response, ContractError> {
+    // MsgSwap is not a supported CosmosMsg, so we construct the proto manually
+    let msg_swap = proto_encode(MsgSwap {
+        trader: env.contract.address.to_string(),
+        offer_coin: Some(ProtoCoin {
+            denom: offer_coin.denom.clone(),
+            amount: offer_coin.amount.to_string(),
+        }),
+        ask_denom: ask_denom.clone(),
+    })?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_always(
+            CosmosMsg::Stargate {
+                type_url: "/terra.market.v1beta1.MsgSwap".to_string(),
+                value: msg_swap.into(),
+            },
+            reply_id,
+        ))
+        .add_attribute("swap_offer_amount", offer_coin.amount)
+        .add_attribute("swap_offer_denom", offer_coin.denom)
+        .add_attribute("swap_ask_denom", ask_denom))
+}
@@ -0,0 +1,71 @@
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::{Addr, Deps, Response, StdResult, Storage, Uint128};
+#[cfg(test)]
+use std::cell::Cell;
+
+use crate::anchor::AnchorStrategy;
+use crate::state::{config_read, Config, YieldBackend};
+
+/// Money market holding the contract's stablecoins. Deposits are held as position tokens,
+/// 1 aliceUST is backed by 1 position token
+pub trait YieldStrategy {
+    /// Returns response with submessage to deposit stable_amount, replied to reply_id.
+    /// Warning: does not account for Terra tax.
+    fn deposit_stable(&self, stable_amount: Uint128, reply_id: u64) -> StdResult<Response>;
+
+    /// Returns response with submessage to redeem position_amount, replied to reply_id
+    fn redeem_stable(&self, position_amount: Uint128, reply_id: u64) -> StdResult<Response>;
+
+    /// Position tokens held by account
+    fn query_position(&self, deps: Deps, account: Addr) -> StdResult<Uint128>;
+
+    /// Whether a failed redeem submessage error is the market lacking stablecoin liquidity
+    fn is_illiquid_error(&self, error: &str) -> bool;
+
+    /// Returns the stablecoin/position token exchange rate at block_height
+    fn query_exchange_rate(&self, deps: Deps, block_height: u64) -> StdResult<Decimal256>;
+
+    /// Stablecoin value of the position tokens held by account at block_height
+    fn query_position_value(
+        &self,
+        deps: Deps,
+        account: Addr,
+        block_height: u64,
+    ) -> StdResult<Uint128> {
+        let position = self.query_position(deps, account)?;
+        let exchange_rate = self.query_exchange_rate(deps, block_height)?;
+        Ok(Uint128::from(exchange_rate * Uint256::from(position)))
+    }
+}
+
+/// Constructor of the strategy replacing the config yield backend
+#[cfg(test)]
+pub type TestStrategy = fn(&Config) -> Box<dyn YieldStrategy>;
+
+#[cfg(test)]
+thread_local! {
+    static TEST_STRATEGY: Cell<Option<TestStrategy>> = Cell::new(None);
+}
+
+/// Replaces the config yield backend for the rest of the current test
+#[cfg(test)]
+pub fn set_test_strategy(strategy: TestStrategy) {
+    TEST_STRATEGY.with(|test_strategy| test_strategy.set(Some(strategy)));
+}
+
+/// Strategy of the config yield backend
+pub fn yield_strategy(config: &Config) -> Box<dyn YieldStrategy> {
+    #[cfg(test)]
+    if let Some(strategy) = TEST_STRATEGY.with(Cell::get) {
+        return strategy(config);
+    }
+
+    match config.yield_backend {
+        YieldBackend::Anchor => Box::new(AnchorStrategy::new(config)),
+    }
+}
+
+pub fn load_yield_strategy(storage: &dyn Storage) -> StdResult<Box<dyn YieldStrategy>> {
+    let config = config_read(storage).load()?;
+    Ok(yield_strategy(&config))
+}
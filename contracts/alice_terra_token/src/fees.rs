@@ -0,0 +1,148 @@
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::{Addr, Api, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
+
+use crate::error::ContractError;
+use crate::msg::FeeRecipientMsg;
+use crate::referral::transfer_referral_share;
+use crate::snapshot::{execute_mint, execute_transfer};
+use crate::state::{config_read, fee_exemptions_mut, fee_exemptions_read, Config, FeeRecipient};
+
+pub fn validate_fee_recipients(
+    api: &dyn Api,
+    fee_recipients: Vec<FeeRecipientMsg>,
+) -> StdResult<Vec<FeeRecipient>> {
+    fee_recipients
+        .into_iter()
+        .map(|fee_recipient| {
+            Ok(FeeRecipient {
+                address: api.addr_validate(&fee_recipient.address)?,
+                share: fee_recipient.share,
+            })
+        })
+        .collect()
+}
+
+/// Owner and exempted addresses pay no deposit & redeem fees
+pub fn is_fee_exempt(storage: &dyn Storage, config: &Config, address: &Addr) -> StdResult<bool> {
+    if *address == config.owner {
+        return Ok(true);
+    }
+    Ok(fee_exemptions_read(storage)
+        .may_load(address.as_bytes())?
+        .unwrap_or(false))
+}
+
+/// Redeem fee ratio of the highest tier reached by burn_amount
+pub fn redeem_fee_ratio(config: &Config, burn_amount: Uint128) -> Decimal256 {
+    config
+        .redeem_fee_tiers
+        .iter()
+        .rev()
+        .find(|tier| burn_amount >= tier.min_amount)
+        .map_or(config.redeem_fee_ratio, |tier| tier.fee_ratio)
+}
+
+/// Splits fee_amount across the fee recipients, or all to owner if there are none.
+/// The last recipient receives the rounding remainder.
+pub fn split_fee(config: &Config, fee_amount: Uint128) -> Vec<(Addr, Uint128)> {
+    if config.fee_recipients.is_empty() {
+        return vec![(config.owner.clone(), fee_amount)];
+    }
+
+    let mut remaining_amount = fee_amount;
+    config
+        .fee_recipients
+        .iter()
+        .enumerate()
+        .map(|(i, fee_recipient)| {
+            let amount = if i == config.fee_recipients.len() - 1 {
+                remaining_amount
+            } else {
+                Uint128::from(fee_recipient.share * Uint256::from(fee_amount))
+            };
+            remaining_amount -= amount;
+            (fee_recipient.address.clone(), amount)
+        })
+        .collect()
+}
+
+/// Transfers fee_amount aliceUST from info.sender to its referrer & the fee recipients
+pub fn transfer_fee(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &Config,
+    fee_amount: Uint128,
+) -> Result<(), ContractError> {
+    let referral_amount =
+        transfer_referral_share(deps.branch(), env.clone(), info.clone(), config, fee_amount)?;
+    let fee_amount = fee_amount - referral_amount;
+
+    for (fee_recipient, amount) in split_fee(config, fee_amount) {
+        if amount.is_zero() {
+            continue;
+        }
+        execute_transfer(
+            deps.branch(),
+            env.clone(),
+            info.clone(),
+            fee_recipient.to_string(),
+            amount,
+        )?;
+    }
+    Ok(())
+}
+
+/// Mints fee_amount aliceUST to the fee recipients
+pub fn mint_fee(
+    mut deps: DepsMut,
+    env: Env,
+    config: &Config,
+    fee_amount: Uint128,
+) -> Result<(), ContractError> {
+    for (fee_recipient, amount) in split_fee(config, fee_amount) {
+        if amount.is_zero() {
+            continue;
+        }
+        // call execute_mint as contract self (no one else has permission)
+        execute_mint(
+            deps.branch(),
+            env.clone(),
+            MessageInfo {
+                sender: env.contract.address.clone(),
+                funds: vec![],
+            },
+            fee_recipient.to_string(),
+            amount,
+        )?;
+    }
+    Ok(())
+}
+
+pub fn execute_update_fee_exemptions(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Option<Vec<String>>,
+    remove: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut response = Response::new();
+    for address in add.unwrap_or_default() {
+        let address = deps.api.addr_validate(&address)?;
+        fee_exemptions_mut(deps.storage).save(address.as_bytes(), &true)?;
+        response = response.add_attribute("fee_exemption_added", address);
+    }
+    for address in remove.unwrap_or_default() {
+        let address = deps.api.addr_validate(&address)?;
+        fee_exemptions_mut(deps.storage).remove(address.as_bytes());
+        response = response.add_attribute("fee_exemption_removed", address);
+    }
+
+    Ok(response)
+}
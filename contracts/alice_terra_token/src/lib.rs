@@ -1,15 +1,33 @@
 mod external;
 
 pub use external::anchor;
+pub use external::authz;
+pub use external::terra_market;
+pub use external::yield_strategy;
 pub mod contract;
+pub mod earnings;
+pub mod emergency_exit;
+pub mod enumerable;
 pub mod error;
 pub mod execute;
+pub mod fees;
+pub mod marketing;
 pub mod migrate;
 pub mod msg;
+pub mod operators;
+pub mod ownership;
+pub mod pause;
 pub mod query;
+pub mod rate_history;
+pub mod referral;
 pub mod relay;
+pub mod rescue;
+pub mod schedules;
+pub mod skim;
+pub mod snapshot;
 pub mod state;
 pub mod utils;
+pub mod withdrawal_queue;
 
 #[cfg(test)]
 mod testing;
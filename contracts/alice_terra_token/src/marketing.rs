@@ -0,0 +1,54 @@
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+use cw20::Logo;
+use cw20_base::contract;
+use cw20_base::state::MARKETING_INFO;
+
+use crate::error::ContractError;
+use crate::state::config_read;
+
+/// Sets owner as the cw20 marketing role, so cw20-base accepts the owner's updates
+fn set_marketing_owner(storage: &mut dyn Storage, owner: &Addr) -> StdResult<()> {
+    let mut marketing_info = MARKETING_INFO.may_load(storage)?.unwrap_or_default();
+    marketing_info.marketing = Some(owner.clone());
+    MARKETING_INFO.save(storage, &marketing_info)
+}
+
+pub fn execute_update_marketing(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    project: Option<String>,
+    description: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    set_marketing_owner(deps.storage, &config.owner)?;
+    Ok(contract::execute_update_marketing(
+        deps,
+        env,
+        info,
+        project,
+        description,
+        None,
+    )?)
+}
+
+pub fn execute_upload_logo(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    logo: Logo,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    set_marketing_owner(deps.storage, &config.owner)?;
+    Ok(contract::execute_upload_logo(deps, env, info, logo)?)
+}
@@ -1,7 +1,13 @@
+use crate::fees::validate_fee_recipients;
 use crate::msg::MigrateMsg;
-use crate::state::{save_config, Config, CONFIG_KEY};
+#[cfg(feature = "snapshot")]
+use crate::snapshot::init_snapshots;
+use crate::state::{
+    holder_count_read, holder_recount_mut, holder_recount_read, save_config, Config, FeeRecipient,
+    HolderRecount, RedeemFeeTier, TaxPolicy, YieldBackend, CONFIG_KEY,
+};
 use cosmwasm_bignumber::Decimal256;
-use cosmwasm_std::{Addr, DepsMut, StdResult, Storage};
+use cosmwasm_std::{Addr, DepsMut, StdResult, Storage, Uint128};
 use cosmwasm_storage::{singleton_read, ReadonlySingleton};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -19,19 +25,35 @@ pub struct LegacyConfig {
     pub aterra_token_addr: Addr,
     /// Redeem fee ratio between 0 and 1
     pub redeem_fee_ratio: Option<Decimal256>,
+    /// Terra native denoms accepted for deposit besides stable_denom
+    pub swap_denoms: Option<Vec<String>>,
+    /// Max aliceUST balance an address can reach by deposits, none is unlimited
+    pub address_deposit_cap: Option<Uint128>,
+    /// Max total aliceUST supply reachable by deposits
+    pub total_deposit_cap: Option<Uint128>,
+    /// Deposit fee ratio between 0 and 1
+    pub deposit_fee_ratio: Option<Decimal256>,
+    /// Accounts that split fees & relay tips
+    pub fee_recipients: Option<Vec<FeeRecipient>>,
+    /// Redeem fee ratios by burn amount
+    pub redeem_fee_tiers: Option<Vec<RedeemFeeTier>>,
+    /// Share of referred users' redeem fees & relay tips that goes to their referrer
+    pub referral_share: Option<Decimal256>,
+    /// Account that can pause & unpause the contract
+    pub guardian: Option<Addr>,
+    /// Tax charged on native transfers
+    pub tax_policy: Option<TaxPolicy>,
+    /// Money market holding the deposited stablecoins
+    pub yield_backend: Option<YieldBackend>,
 }
 
-fn legacy_config_read(storage: &dyn Storage) -> ReadonlySingleton<LegacyConfig> {
+fn legacy_config_read(storage: &dyn Storage) -> ReadonlySingleton<'_, LegacyConfig> {
     singleton_read(storage, CONFIG_KEY)
 }
 
 pub fn migrate_config(deps: DepsMut, msg: MigrateMsg) -> StdResult<()> {
     let mut legacy_config = legacy_config_read(deps.storage).load()?;
 
-    if let Some(owner) = msg.owner {
-        legacy_config.owner = deps.api.addr_validate(&owner)?;
-    }
-
     if let Some(money_market_addr) = msg.money_market_addr {
         legacy_config.money_market_addr = deps.api.addr_validate(&money_market_addr)?;
     }
@@ -44,6 +66,30 @@ pub fn migrate_config(deps: DepsMut, msg: MigrateMsg) -> StdResult<()> {
         legacy_config.redeem_fee_ratio = Some(redeem_fee_ratio);
     }
 
+    if let Some(swap_denoms) = msg.swap_denoms {
+        legacy_config.swap_denoms = Some(swap_denoms);
+    }
+
+    if let Some(deposit_fee_ratio) = msg.deposit_fee_ratio {
+        legacy_config.deposit_fee_ratio = Some(deposit_fee_ratio);
+    }
+
+    if let Some(fee_recipients) = msg.fee_recipients {
+        legacy_config.fee_recipients = Some(validate_fee_recipients(deps.api, fee_recipients)?);
+    }
+
+    if let Some(guardian) = msg.guardian {
+        legacy_config.guardian = Some(deps.api.addr_validate(&guardian)?);
+    }
+
+    if let Some(tax_policy) = msg.tax_policy {
+        legacy_config.tax_policy = Some(tax_policy);
+    }
+
+    if let Some(yield_backend) = msg.yield_backend {
+        legacy_config.yield_backend = Some(yield_backend);
+    }
+
     save_config(
         deps.storage,
         &Config {
@@ -54,8 +100,44 @@ pub fn migrate_config(deps: DepsMut, msg: MigrateMsg) -> StdResult<()> {
             redeem_fee_ratio: legacy_config
                 .redeem_fee_ratio
                 .unwrap_or_else(Decimal256::zero),
+            swap_denoms: legacy_config.swap_denoms.unwrap_or_default(),
+            address_deposit_cap: legacy_config.address_deposit_cap,
+            total_deposit_cap: legacy_config.total_deposit_cap,
+            deposit_fee_ratio: legacy_config
+                .deposit_fee_ratio
+                .unwrap_or_else(Decimal256::zero),
+            fee_recipients: legacy_config.fee_recipients.unwrap_or_default(),
+            redeem_fee_tiers: legacy_config.redeem_fee_tiers.unwrap_or_default(),
+            referral_share: legacy_config
+                .referral_share
+                .unwrap_or_else(Decimal256::zero),
+            guardian: legacy_config.guardian,
+            tax_policy: legacy_config.tax_policy.unwrap_or_default(),
+            yield_backend: legacy_config.yield_backend.unwrap_or_default(),
         },
     )?;
 
     Ok(())
 }
+
+/// Starts a holder recount when migrating from a version without the holder count,
+/// existing balances are counted by RecountHolders
+pub fn migrate_holder_count(storage: &mut dyn Storage) -> StdResult<()> {
+    if holder_count_read(storage).may_load()?.is_none()
+        && holder_recount_read(storage).may_load()?.is_none()
+    {
+        holder_recount_mut(storage).save(&HolderRecount {
+            start_after: None,
+            holder_count: 0,
+        })?;
+    }
+    Ok(())
+}
+
+/// Starts balance & total supply snapshots when migrating from the cw20-base layout.
+/// Balance snapshots read the existing cw20-base BALANCES as current values, so balances
+/// need no copy. Queries before the migration height are rejected
+#[cfg(feature = "snapshot")]
+pub fn migrate_snapshots(storage: &mut dyn Storage, height: u64) -> StdResult<()> {
+    init_snapshots(storage, height)
+}
@@ -1,5 +1,12 @@
 use cosmwasm_bignumber::Decimal256;
-use cosmwasm_std::{Binary, Uint128};
+use cosmwasm_std::{Binary, Timestamp, Uint128};
+use cw0::Expiration;
+use cw20::Logo;
+
+use crate::state::{
+    EmergencyExit, PendingOwner, RateSnapshot, RedeemFeeTier, Schedule, TaxPolicy,
+    WithdrawalTicket, YieldBackend,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +28,56 @@ pub struct InstantiateMsg {
     pub aterra_token_addr: String,
     /// Redeem fee ratio between 0 and 1, default 0
     pub redeem_fee_ratio: Decimal256,
+    /// Terra native denoms accepted for deposit besides stable_denom, default none
+    pub swap_denoms: Option<Vec<String>>,
+    /// Max total aliceUST supply (= aUST held) reachable by deposits, default unlimited
+    pub total_deposit_cap: Option<Uint128>,
+    /// Max aliceUST balance an address can reach by deposits, default unlimited
+    pub address_deposit_cap: Option<Uint128>,
+    /// Deposit fee ratio between 0 and 1, default 0
+    pub deposit_fee_ratio: Option<Decimal256>,
+    /// Accounts that split fees & relay tips, default all to owner
+    pub fee_recipients: Option<Vec<FeeRecipientMsg>>,
+    /// Redeem fee ratios by burn amount sorted by min_amount, default none
+    pub redeem_fee_tiers: Option<Vec<RedeemFeeTier>>,
+    /// Share of referred users' redeem fees & relay tips that goes to their referrer, default 0
+    pub referral_share: Option<Decimal256>,
+    /// Account that can pause & unpause the contract, default none
+    pub guardian: Option<String>,
+    /// Tax charged on native transfers, default Terra treasury tax
+    pub tax_policy: Option<TaxPolicy>,
+    /// Money market holding the deposited stablecoins, default Anchor
+    pub yield_backend: Option<YieldBackend>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeRecipientMsg {
+    pub address: String,
+    /// Share of each fee between 0 and 1, shares of all recipients sum to 1
+    pub share: Decimal256,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DepositCapsMsg {
+    /// Max total aliceUST supply, none is unlimited
+    pub total_cap: Option<Uint128>,
+    /// Max aliceUST balance per address reachable by deposits, none is unlimited.
+    /// Soft limit, holders can transfer out & deposit again
+    pub address_cap: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuthorizedDepositMsg {
+    pub recipient: String,
+    /// Stablecoin amount deposited for recipient
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OperatorMsg {
+    pub address: String,
+    /// Max stablecoin amount deposited per day, none is unlimited
+    pub daily_limit: Option<Uint128>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -40,6 +97,7 @@ pub struct MetaTx {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     Relay {
         /// MetaTx JSON serialized
@@ -50,24 +108,161 @@ pub enum ExecuteMsg {
         public_key: Binary,
     },
     /// Use a SendAuthorization to retrieve the stablecoin amount from 'sender'
-    /// Only executable by owner
+    /// Only executable by owner or operators, within their daily limit
     DepositStableAuthorized {
         #[deprecated]
         sender: Option<String>, // ignored
         recipient: String,
         amount: Uint128,
+        /// Account that referred recipient, only recorded on its first referral
+        referrer: Option<String>,
+    },
+    /// Use granter's SendAuthorization to retrieve the total stablecoin amount of deposits
+    /// and deposit each amount for its recipient. Sent funds must pay 2*tax on the total.
+    /// Only executable by granter, directly or by relay
+    DepositStableAuthorizedBatch {
+        granter: String,
+        deposits: Vec<AuthorizedDepositMsg>,
     },
-    /// MUST be the config stable denomination
+    /// MUST be the config stable denomination or one of the config swap denominations
     DepositStable {
         /// Default is tx sender
         recipient: Option<String>,
+        /// Minimum stablecoin amount returned by the market swap, only for swap denominations
+        min_stable_amount: Option<Uint128>,
+        /// Account that referred recipient, only recorded on its first referral, sent by recipient
+        referrer: Option<String>,
     },
     RedeemStable {
         /// Default is tx sender
         recipient: Option<String>,
         /// Amount in aliceUST
         burn_amount: Uint128,
+        /// If Anchor can't honor the redemption, escrow burn_amount into a
+        /// withdrawal ticket instead of failing. Default is false.
+        /// Queued withdrawals are always paid in the stable denom
+        queue_if_illiquid: Option<bool>,
+        /// Swap redeemed stablecoins to one of the config swap denominations. Default is stable denom
+        target_denom: Option<String>,
+        /// Minimum amount returned by the market swap, only for target_denom
+        min_target_amount: Option<Uint128>,
+    },
+    /// Fulfill queued withdrawal tickets in FIFO order. Executable by anyone
+    ProcessWithdrawals {
+        /// Max number of tickets to fulfill, default 10
+        limit: Option<u32>,
+    },
+    /// Deposit amount from sender's SendAuthorization every interval seconds until end.
+    /// The first deposit is due immediately
+    CreateSchedule {
+        /// At least 1 UST, at most 10 schedules per sender
+        amount: Uint128,
+        /// Seconds between deposits, at least 1 hour
+        interval: u64,
+        end: Option<Timestamp>,
+    },
+    /// Cancel a schedule. Only executable by the schedule owner
+    CancelSchedule { id: u64 },
+    /// Deposit due schedules in FIFO due order. Executable by anyone,
+    /// sent funds must pay the tax of each owner's MsgSend and of the Anchor deposit,
+    /// the rest is refunded
+    ProcessSchedules {
+        /// Max number of schedules to process, default 10
+        limit: Option<u32>,
+    },
+    /// Record the Anchor exchange rate, at most once per hour. Executable by anyone.
+    /// Also recorded on deposit & redeem
+    RecordRate {},
+    /// Count holders among the next balances, after migrating from a version without
+    /// the holder count. Executable by anyone
+    RecountHolders {
+        /// Max number of balances to count, default 30
+        limit: Option<u32>,
+    },
+    /// Cancel a withdrawal ticket and return the escrowed aliceUST.
+    /// Only executable by the ticket owner
+    CancelWithdrawal { id: u64 },
+    /// Propose a new owner, who must accept the ownership before expires.
+    /// Only executable by owner
+    ProposeNewOwner {
+        owner: String,
+        /// Default never
+        expires: Option<Expiration>,
+    },
+    /// Become owner. Only executable by the pending owner
+    AcceptOwnership {},
+    /// Only executable by owner
+    CancelOwnershipProposal {},
+    /// Update low-risk config fields, unset fields are unchanged. Anchor addresses & guardian
+    /// are only changed by migration, owner by ownership transfer. Only executable by owner
+    UpdateConfig {
+        /// Redeem fee ratio below the first tier
+        redeem_fee_ratio: Option<Decimal256>,
+        /// Redeem fee ratios by burn amount sorted by min_amount, replaces all tiers
+        redeem_fee_tiers: Option<Vec<RedeemFeeTier>>,
+        deposit_fee_ratio: Option<Decimal256>,
+        /// Share of referred users' redeem fees & relay tips that goes to their referrer
+        referral_share: Option<Decimal256>,
+        /// Accounts that split fees & relay tips, replaces all
+        fee_recipients: Option<Vec<FeeRecipientMsg>>,
+        /// Deposit caps in aliceUST, replaces both caps
+        deposit_caps: Option<DepositCapsMsg>,
+        /// Terra native denoms accepted for deposit besides stable_denom, replaces all
+        swap_denoms: Option<Vec<String>>,
+        /// Tax charged on native transfers
+        tax_policy: Option<TaxPolicy>,
+    },
+    /// Add or remove addresses that pay no deposit & redeem fees as recipient.
+    /// Only executable by owner
+    UpdateFeeExemptions {
+        add: Option<Vec<String>>,
+        remove: Option<Vec<String>>,
     },
+    /// Transfer aUST held beyond the aliceUST supply to recipient.
+    /// Only executable by owner
+    SkimSurplus { recipient: String },
+    /// Transfer CW20 tokens mistakenly sent to the contract, except aUST & aliceUST.
+    /// Only executable by owner
+    RescueCw20 {
+        token: String,
+        amount: Uint128,
+        recipient: String,
+    },
+    /// Send native tokens mistakenly sent to the contract, except stable denom.
+    /// Only executable by owner
+    RescueNative {
+        denom: String,
+        amount: Uint128,
+        recipient: String,
+    },
+    /// Pause the switches set to true, the others are unchanged.
+    /// Only executable by guardian
+    Pause {
+        deposits: bool,
+        redeems: bool,
+        transfers: bool,
+        relay: bool,
+    },
+    /// Unpause all switches. Only executable by guardian
+    Unpause {},
+    /// Redeem all aUST from Anchor. Afterwards RedeemStable pays a pro-rata share of the
+    /// UST held and deposits are disabled. Only executable by owner or guardian
+    EmergencyExit {},
+    /// Add or update operators that may call DepositStableAuthorized, or remove them.
+    /// Only executable by owner
+    UpdateOperators {
+        add: Option<Vec<OperatorMsg>>,
+        remove: Option<Vec<String>>,
+    },
+    /// Implements CW20 "marketing" extension. Updates the project URL & description,
+    /// empty strings unset them. Only executable by owner
+    UpdateMarketing {
+        project: Option<String>,
+        description: Option<String>,
+    },
+    /// Implements CW20 "marketing" extension. Uploads a URL, SVG or PNG logo.
+    /// Only executable by owner
+    UploadLogo(Logo),
     /// Implements CW20. Transfer is a base message to move tokens to another account without triggering actions
     Transfer { recipient: String, amount: Uint128 },
     /// Implements CW20. Burn is a base message to destroy tokens forever
@@ -88,24 +283,109 @@ pub enum QueryMsg {
     RelayNonce { address: String },
     /// Returns the redeem fee ratio
     Config {},
+    /// Remaining deposit capacity in aliceUST, optionally for the given address
+    DepositCapacity { address: Option<String> },
+    /// Current pause switches
+    PauseState {},
+    /// Owner proposed by ProposeNewOwner, until accepted or cancelled
+    PendingOwner {},
+    /// Emergency exit status
+    EmergencyExit {},
+    /// aliceUST supply against the aUST & UST held by the contract
+    Solvency {},
+    /// Total surplus aUST skimmed by the owner
+    SkimmedTotal {},
+    /// Pending withdrawal tickets owned by the given address
+    WithdrawalTickets {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Deposit schedules owned by the given address
+    Schedules {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// UST principal, current value & lifetime yield of the given address
+    Earnings { address: String },
+    /// Recorded exchange rates in ascending time order
+    RateHistory {
+        /// Block time in seconds
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Trailing 7 & 30 day APY from the recorded exchange rates
+    Apy {},
+    /// Deposit fee ratio & redeem fee tiers
+    FeeSchedule {},
+    /// Users referred by the given referrer
+    Referrals {
+        referrer: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Total aliceUST the given referrer earned from referrals
+    ReferralEarnings { referrer: String },
+    /// Addresses exempt from deposit & redeem fees, besides owner
+    FeeExemptions {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Operators that may call DepositStableAuthorized & their usage today
+    Operators {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Implements CW20. Returns the current balance of the given address, 0 if unset.
     Balance { address: String },
+    /// Balance of the given address at the start of the given block height, 0 if unset.
+    /// Requires the snapshot feature
+    #[cfg(feature = "snapshot")]
+    BalanceAtHeight { address: String, height: u64 },
+    /// Total supply at the start of the given block height. Requires the snapshot feature
+    #[cfg(feature = "snapshot")]
+    TotalSupplyAtHeight { height: u64 },
     /// Implements CW20. Returns metadata on the contract - name, decimals, supply, etc.
     TokenInfo {},
+    /// Implements CW20 "enumerable" extension. Returns all accounts that have balances,
+    /// including those that hold none anymore
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Number of addresses with a nonzero aliceUST balance, fails until a holder recount is done
+    HolderCount {},
+    /// Implements CW20 "marketing" extension. Returns the project URL, description,
+    /// logo info & owner as marketing role
+    MarketingInfo {},
+    /// Implements CW20 "marketing" extension. Returns the embedded logo data, not found
+    /// for URL logos
+    DownloadLogo {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MigrateMsg {
     /// symbol / ticker of the derivative token
     pub symbol: Option<String>,
-    /// account that collects relay tips & redeem fees
-    pub owner: Option<String>,
     /// Anchor Money Market Contract address
     pub money_market_addr: Option<String>,
     /// Anchor aTerra Token Contract address
     pub aterra_token_addr: Option<String>,
     /// Redeem fee ratio between 0 and 1
     pub redeem_fee_ratio: Option<Decimal256>,
+    /// Terra native denoms accepted for deposit besides stable_denom
+    pub swap_denoms: Option<Vec<String>>,
+    /// Deposit fee ratio between 0 and 1
+    pub deposit_fee_ratio: Option<Decimal256>,
+    /// Accounts that split fees & relay tips, empty means all to owner
+    pub fee_recipients: Option<Vec<FeeRecipientMsg>>,
+    /// Account that can pause & unpause the contract
+    pub guardian: Option<String>,
+    /// Tax charged on native transfers
+    pub tax_policy: Option<TaxPolicy>,
+    /// Money market holding the deposited stablecoins
+    pub yield_backend: Option<YieldBackend>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -119,3 +399,131 @@ pub struct RelayNonceResponse {
     /// Current relay nonce. Add 1 in new tx.
     pub relay_nonce: Uint128,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalTicketsResponse {
+    pub tickets: Vec<WithdrawalTicket>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SchedulesResponse {
+    pub schedules: Vec<Schedule>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SolvencyResponse {
+    /// Total aliceUST supply
+    pub total_supply: Uint128,
+    /// aUST held by the contract, backs aliceUST 1:1. Zero after the emergency exit
+    pub aterra_balance: Uint128,
+    /// UST held by the contract, e.g. redeemed for queued withdrawals
+    pub stable_reserve: Uint128,
+    /// aUST held beyond total_supply. After the emergency exit, the UST held valued
+    /// in aUST at the exit rate backs aliceUST instead
+    pub surplus: Uint128,
+    /// total_supply not backed by aUST, or by the UST held after the emergency exit
+    pub deficit: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingOwnerResponse {
+    pub pending_owner: Option<PendingOwner>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmergencyExitResponse {
+    /// None until the emergency exit
+    pub exit: Option<EmergencyExit>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SkimmedTotalResponse {
+    /// aUST transferred out by SkimSurplus
+    pub skimmed_total: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DepositCapacityResponse {
+    /// Max total aliceUST supply, none is unlimited
+    pub total_cap: Option<Uint128>,
+    /// aliceUST that can still be minted before reaching total_cap
+    pub total_remaining: Option<Uint128>,
+    /// Max aliceUST balance per address, none is unlimited
+    pub address_cap: Option<Uint128>,
+    /// aliceUST the given address can still receive from deposits before reaching address_cap
+    pub address_remaining: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeScheduleResponse {
+    pub deposit_fee_ratio: Decimal256,
+    /// Redeem fee ratio below the first tier
+    pub redeem_fee_ratio: Decimal256,
+    pub redeem_fee_tiers: Vec<RedeemFeeTier>,
+    pub referral_share: Decimal256,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeExemptionsResponse {
+    pub addresses: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OperatorInfo {
+    pub address: String,
+    /// Max stablecoin amount deposited per day, none is unlimited
+    pub daily_limit: Option<Uint128>,
+    /// Stablecoin amount deposited today
+    pub used_today: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OperatorsResponse {
+    pub operators: Vec<OperatorInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReferralsResponse {
+    pub users: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReferralEarningsResponse {
+    /// Number of referred users
+    pub referral_count: u64,
+    /// Total aliceUST earned from redeem fees & relay tips
+    pub earnings: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HolderCountResponse {
+    pub holder_count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EarningsResponse {
+    /// Net UST deposited, including aliceUST transferred in & out at the exchange rate of the transfer
+    pub principal: Uint128,
+    /// UST value of the aliceUST balance at the current exchange rate
+    pub current_value: Uint128,
+    /// UST earned over the lifetime of the address, after fees
+    pub lifetime_yield: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateHistoryResponse {
+    pub snapshots: Vec<RateSnapshot>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApyResponse {
+    /// None until 7 days of exchange rates are recorded
+    pub apy_7d: Option<Decimal256>,
+    /// None until 30 days of exchange rates are recorded
+    pub apy_30d: Option<Decimal256>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalSupplyResponse {
+    pub total_supply: Uint128,
+}
@@ -0,0 +1,100 @@
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
+
+use crate::error::ContractError;
+use crate::msg::OperatorMsg;
+use crate::state::{config_read, operators_mut, operators_read, Config, Operator};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Days since the unix epoch, operator usage resets at the start of each day
+pub fn current_day(env: &Env) -> u64 {
+    env.block.time.seconds() / SECONDS_PER_DAY
+}
+
+/// Amount deposited by operator on the given day
+pub fn used_on_day(operator: &Operator, day: u64) -> Uint128 {
+    if operator.day == day {
+        operator.used
+    } else {
+        Uint128::zero()
+    }
+}
+
+/// Owner may always call DepositStableAuthorized. Operators may until their daily limit
+/// is reached, the amount is added to their usage
+pub fn use_operator_limit(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    sender: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if *sender == config.owner {
+        return Ok(());
+    }
+
+    let mut operator = operators_read(storage)
+        .may_load(sender.as_bytes())?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    let day = current_day(env);
+    let used = used_on_day(&operator, day) + amount;
+    if let Some(daily_limit) = operator.daily_limit {
+        if used > daily_limit {
+            return Err(ContractError::OperatorDailyLimitReached {});
+        }
+    }
+
+    operator.day = day;
+    operator.used = used;
+    operators_mut(storage).save(sender.as_bytes(), &operator)?;
+    Ok(())
+}
+
+fn update_operator(
+    storage: &mut dyn Storage,
+    operator_msg: &OperatorMsg,
+    address: &Addr,
+) -> StdResult<()> {
+    // Keep today's usage when changing the limit of an existing operator
+    let operator = match operators_read(storage).may_load(address.as_bytes())? {
+        Some(operator) => Operator {
+            daily_limit: operator_msg.daily_limit,
+            ..operator
+        },
+        None => Operator {
+            daily_limit: operator_msg.daily_limit,
+            day: 0,
+            used: Uint128::zero(),
+        },
+    };
+    operators_mut(storage).save(address.as_bytes(), &operator)
+}
+
+pub fn execute_update_operators(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Option<Vec<OperatorMsg>>,
+    remove: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut response = Response::new();
+    for operator_msg in add.unwrap_or_default() {
+        let address = deps.api.addr_validate(&operator_msg.address)?;
+        update_operator(deps.storage, &operator_msg, &address)?;
+        response = response.add_attribute("operator_added", address);
+    }
+    for address in remove.unwrap_or_default() {
+        let address = deps.api.addr_validate(&address)?;
+        operators_mut(deps.storage).remove(address.as_bytes());
+        response = response.add_attribute("operator_removed", address);
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,88 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cw0::Expiration;
+
+use crate::error::ContractError;
+use crate::state::{config_read, pending_owner_mut, save_config, PendingOwner};
+
+/// Proposes a new owner, who becomes owner once accepting before expires.
+/// Replaces any previous proposal
+pub fn execute_propose_new_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let owner = deps.api.addr_validate(&owner)?;
+    if owner == config.owner {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    pending_owner_mut(deps.storage).save(&PendingOwner {
+        owner: owner.clone(),
+        expires,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("pending_owner", owner)
+        .add_attribute("expires", expires.to_string()))
+}
+
+pub fn execute_accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = config_read(deps.storage).load()?;
+
+    let mut pending_owner = pending_owner_mut(deps.storage);
+    let PendingOwner { owner, expires } = pending_owner
+        .may_load()?
+        .ok_or(ContractError::NoPendingOwner {})?;
+    if info.sender != owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    pending_owner.remove();
+
+    let prev_owner = config.owner;
+    config.owner = owner;
+    save_config(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("prev_owner", prev_owner)
+        .add_attribute("owner", config.owner))
+}
+
+pub fn execute_cancel_ownership_proposal(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut pending_owner = pending_owner_mut(deps.storage);
+    if pending_owner.may_load()?.is_none() {
+        return Err(ContractError::NoPendingOwner {});
+    }
+    pending_owner.remove();
+
+    Ok(Response::new())
+}
@@ -0,0 +1,82 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{config_read, pause_state_mut, pause_state_read, Config, PauseState};
+
+fn assert_guardian(config: &Config, info: &MessageInfo) -> Result<(), ContractError> {
+    match &config.guardian {
+        Some(guardian) if *guardian == info.sender => Ok(()),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+pub fn load_pause_state(storage: &dyn Storage) -> StdResult<PauseState> {
+    Ok(pause_state_read(storage).may_load()?.unwrap_or_default())
+}
+
+/// Rejects msg if its switch is paused
+pub fn assert_not_paused(storage: &dyn Storage, msg: &ExecuteMsg) -> Result<(), ContractError> {
+    let pause_state = load_pause_state(storage)?;
+    let paused = match msg {
+        ExecuteMsg::DepositStable { .. }
+        | ExecuteMsg::DepositStableAuthorized { .. }
+        | ExecuteMsg::DepositStableAuthorizedBatch { .. }
+        | ExecuteMsg::ProcessSchedules { .. } => pause_state.deposits,
+        ExecuteMsg::RedeemStable { .. }
+        | ExecuteMsg::Burn { .. }
+        | ExecuteMsg::ProcessWithdrawals { .. } => pause_state.redeems,
+        ExecuteMsg::Transfer { .. }
+        | ExecuteMsg::Send { .. }
+        | ExecuteMsg::CancelWithdrawal { .. } => pause_state.transfers,
+        ExecuteMsg::Relay { .. } => pause_state.relay,
+        _ => false,
+    };
+    if paused {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
+}
+
+fn pause_state_response(pause_state: &PauseState) -> Response {
+    Response::new()
+        .add_attribute("paused_deposits", pause_state.deposits.to_string())
+        .add_attribute("paused_redeems", pause_state.redeems.to_string())
+        .add_attribute("paused_transfers", pause_state.transfers.to_string())
+        .add_attribute("paused_relay", pause_state.relay.to_string())
+}
+
+/// Pauses the switches set to true, the others are unchanged
+pub fn execute_pause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    switches: PauseState,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+    assert_guardian(&config, &info)?;
+
+    let mut pause_state = load_pause_state(deps.storage)?;
+    pause_state.deposits |= switches.deposits;
+    pause_state.redeems |= switches.redeems;
+    pause_state.transfers |= switches.transfers;
+    pause_state.relay |= switches.relay;
+    pause_state_mut(deps.storage).save(&pause_state)?;
+
+    Ok(pause_state_response(&pause_state))
+}
+
+/// Unpauses all switches
+pub fn execute_unpause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+    assert_guardian(&config, &info)?;
+
+    let pause_state = PauseState::default();
+    pause_state_mut(deps.storage).save(&pause_state)?;
+
+    Ok(pause_state_response(&pause_state))
+}
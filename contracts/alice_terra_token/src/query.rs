@@ -1,7 +1,36 @@
-use cosmwasm_std::{Addr, BalanceResponse, BankQuery, Deps, QueryRequest, StdResult, Uint128};
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{
+    Addr, BalanceResponse, BankQuery, Deps, Env, Order, QueryRequest, StdError, StdResult, Uint128,
+};
 
-use crate::msg::RelayNonceResponse;
-use crate::state::nonces_read;
+use cw20::MarketingInfoResponse;
+use cw20_base::state::{BALANCES, TOKEN_INFO};
+
+use crate::emergency_exit::{is_exited, valuation_exchange_rate};
+use crate::msg::{
+    ApyResponse, DepositCapacityResponse, EarningsResponse, EmergencyExitResponse,
+    FeeExemptionsResponse, FeeScheduleResponse, HolderCountResponse, OperatorInfo,
+    OperatorsResponse, PendingOwnerResponse, RateHistoryResponse, ReferralEarningsResponse,
+    ReferralsResponse, RelayNonceResponse, SchedulesResponse, SkimmedTotalResponse,
+    SolvencyResponse, WithdrawalTicketsResponse,
+};
+use crate::operators::{current_day, used_on_day};
+use crate::rate_history::trailing_apy;
+use crate::state::{
+    config_read, emergency_exit_read, fee_exemptions_read, holder_count_read, nonces_read,
+    operators_read, pending_owner_read, rate_snapshots_read, referral_counts_read,
+    referral_earnings_read, referrals_read, schedule_owners_read, schedules_read,
+    skimmed_total_read, withdrawal_ticket_owners_read, withdrawal_tickets_read, PRINCIPALS,
+};
+use crate::yield_strategy::yield_strategy;
+#[cfg(feature = "snapshot")]
+use crate::{
+    msg::TotalSupplyResponse,
+    state::{BALANCE_SNAPSHOTS, SNAPSHOT_START_HEIGHT, TOTAL_SUPPLY_SNAPSHOTS},
+};
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
 
 pub fn query_relay_nonce(deps: Deps, address: String) -> StdResult<RelayNonceResponse> {
     let canonical_addr = deps.api.addr_canonicalize(&address)?;
@@ -18,3 +47,327 @@ pub fn query_native_balance(deps: Deps, account_addr: Addr, denom: String) -> St
     }))?;
     Ok(balance.amount.amount)
 }
+
+/// Range start right after the big-endian key of id or time, doesn't overflow unlike id + 1
+fn start_after_id(id: u64) -> Vec<u8> {
+    let mut key = id.to_be_bytes().to_vec();
+    key.push(0);
+    key
+}
+
+pub fn query_withdrawal_tickets(
+    deps: Deps,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<WithdrawalTicketsResponse> {
+    let owner = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(start_after_id);
+
+    let tickets_read = withdrawal_tickets_read(deps.storage);
+    let tickets = withdrawal_ticket_owners_read(deps.storage, &owner)
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.and_then(|(id, _)| tickets_read.load(&id)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(WithdrawalTicketsResponse { tickets })
+}
+
+pub fn query_schedules(
+    deps: Deps,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SchedulesResponse> {
+    let owner = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(start_after_id);
+
+    let schedules_read = schedules_read(deps.storage);
+    let schedules = schedule_owners_read(deps.storage, &owner)
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.and_then(|(id, _)| schedules_read.load(&id)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(SchedulesResponse { schedules })
+}
+
+pub fn query_deposit_capacity(
+    deps: Deps,
+    address: Option<String>,
+) -> StdResult<DepositCapacityResponse> {
+    let config = config_read(deps.storage).load()?;
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+
+    let total_cap = config.total_deposit_cap;
+    let total_remaining = total_cap.map(|cap| cap.saturating_sub(total_supply));
+
+    let address_remaining = match (config.address_deposit_cap, address) {
+        (Some(cap), Some(address)) => {
+            let addr = deps.api.addr_validate(&address)?;
+            let balance = BALANCES.may_load(deps.storage, &addr)?.unwrap_or_default();
+            Some(cap.saturating_sub(balance))
+        }
+        _ => None,
+    };
+
+    Ok(DepositCapacityResponse {
+        total_cap,
+        total_remaining,
+        address_cap: config.address_deposit_cap,
+        address_remaining,
+    })
+}
+
+pub fn query_solvency(deps: Deps, env: Env) -> StdResult<SolvencyResponse> {
+    let config = config_read(deps.storage).load()?;
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+    let stable_reserve = query_native_balance(
+        deps,
+        env.contract.address.clone(),
+        config.stable_denom.clone(),
+    )?;
+
+    // After the emergency exit, aliceUST is backed by the UST held at the exit rate
+    let (aterra_balance, backing) = if is_exited(deps.storage)? {
+        let exchange_rate = valuation_exchange_rate(deps, env.block.height)?;
+        let backing = if exchange_rate.is_zero() {
+            Uint128::zero()
+        } else {
+            (Uint256::from(stable_reserve) / exchange_rate).into()
+        };
+        (Uint128::zero(), backing)
+    } else {
+        let aterra_balance = yield_strategy(&config).query_position(deps, env.contract.address)?;
+        (aterra_balance, aterra_balance)
+    };
+
+    Ok(SolvencyResponse {
+        total_supply,
+        aterra_balance,
+        stable_reserve,
+        surplus: backing.saturating_sub(total_supply),
+        deficit: total_supply.saturating_sub(backing),
+    })
+}
+
+pub fn query_pending_owner(deps: Deps) -> StdResult<PendingOwnerResponse> {
+    Ok(PendingOwnerResponse {
+        pending_owner: pending_owner_read(deps.storage).may_load()?,
+    })
+}
+
+pub fn query_emergency_exit(deps: Deps) -> StdResult<EmergencyExitResponse> {
+    Ok(EmergencyExitResponse {
+        exit: emergency_exit_read(deps.storage).may_load()?,
+    })
+}
+
+pub fn query_skimmed_total(deps: Deps) -> StdResult<SkimmedTotalResponse> {
+    Ok(SkimmedTotalResponse {
+        skimmed_total: skimmed_total_read(deps.storage)
+            .may_load()?
+            .unwrap_or_default(),
+    })
+}
+
+/// Appends a zero byte to start the range after the given address key
+fn calc_address_range_start(start_after: Option<String>) -> Option<Vec<u8>> {
+    start_after.map(|address| {
+        let mut start = address.into_bytes();
+        start.push(0);
+        start
+    })
+}
+
+fn address_from_key(key: Vec<u8>) -> StdResult<String> {
+    String::from_utf8(key).map_err(|_| StdError::invalid_utf8("address key"))
+}
+
+pub fn query_fee_schedule(deps: Deps) -> StdResult<FeeScheduleResponse> {
+    let config = config_read(deps.storage).load()?;
+    Ok(FeeScheduleResponse {
+        deposit_fee_ratio: config.deposit_fee_ratio,
+        redeem_fee_ratio: config.redeem_fee_ratio,
+        redeem_fee_tiers: config.redeem_fee_tiers,
+        referral_share: config.referral_share,
+    })
+}
+
+pub fn query_fee_exemptions(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<FeeExemptionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = calc_address_range_start(start_after);
+
+    let addresses = fee_exemptions_read(deps.storage)
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.and_then(|(address, _)| address_from_key(address)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(FeeExemptionsResponse { addresses })
+}
+
+pub fn query_operators(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OperatorsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = calc_address_range_start(start_after);
+    let day = current_day(&env);
+
+    let operators = operators_read(deps.storage)
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.and_then(|(address, operator)| {
+                Ok(OperatorInfo {
+                    address: address_from_key(address)?,
+                    daily_limit: operator.daily_limit,
+                    used_today: used_on_day(&operator, day),
+                })
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(OperatorsResponse { operators })
+}
+
+pub fn query_referrals(
+    deps: Deps,
+    referrer: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ReferralsResponse> {
+    let referrer = deps.api.addr_validate(&referrer)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = calc_address_range_start(start_after);
+
+    let users = referrals_read(deps.storage, &referrer)
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.and_then(|(user, _)| address_from_key(user)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ReferralsResponse { users })
+}
+
+pub fn query_referral_earnings(
+    deps: Deps,
+    referrer: String,
+) -> StdResult<ReferralEarningsResponse> {
+    let referrer = deps.api.addr_validate(&referrer)?;
+    let referral_count = referral_counts_read(deps.storage)
+        .may_load(referrer.as_bytes())?
+        .unwrap_or_default();
+    let earnings = referral_earnings_read(deps.storage)
+        .may_load(referrer.as_bytes())?
+        .unwrap_or_default();
+
+    Ok(ReferralEarningsResponse {
+        referral_count,
+        earnings,
+    })
+}
+
+pub fn query_earnings(deps: Deps, env: Env, address: String) -> StdResult<EarningsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let principal = PRINCIPALS
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    let balance = BALANCES
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+
+    let exchange_rate = valuation_exchange_rate(deps, env.block.height)?;
+    let current_value = Uint128::from(exchange_rate * Uint256::from(balance));
+
+    Ok(EarningsResponse {
+        principal: principal.deposited.saturating_sub(principal.withdrawn),
+        current_value,
+        lifetime_yield: (current_value + principal.withdrawn).saturating_sub(principal.deposited),
+    })
+}
+
+pub fn query_rate_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<RateHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(start_after_id);
+
+    let snapshots = rate_snapshots_read(deps.storage)
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, snapshot)| snapshot))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(RateHistoryResponse { snapshots })
+}
+
+pub fn query_apy(deps: Deps) -> StdResult<ApyResponse> {
+    Ok(ApyResponse {
+        apy_7d: trailing_apy(deps, 7)?,
+        apy_30d: trailing_apy(deps, 30)?,
+    })
+}
+
+pub fn query_holder_count(deps: Deps) -> StdResult<HolderCountResponse> {
+    Ok(HolderCountResponse {
+        holder_count: holder_count_read(deps.storage)
+            .may_load()?
+            .ok_or_else(|| StdError::generic_err("Holder recount in progress"))?,
+    })
+}
+
+/// Marketing info with the current owner as marketing role
+pub fn query_marketing_info(deps: Deps) -> StdResult<MarketingInfoResponse> {
+    let config = config_read(deps.storage).load()?;
+    let mut marketing_info = cw20_base::contract::query_marketing_info(deps)?;
+    marketing_info.marketing = Some(config.owner);
+    Ok(marketing_info)
+}
+
+#[cfg(feature = "snapshot")]
+fn assert_snapshot_height(deps: Deps, height: u64) -> StdResult<()> {
+    let start_height = SNAPSHOT_START_HEIGHT.load(deps.storage)?;
+    if height < start_height {
+        return Err(StdError::generic_err(format!(
+            "No snapshots before height {}",
+            start_height
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "snapshot")]
+pub fn query_balance_at_height(
+    deps: Deps,
+    address: String,
+    height: u64,
+) -> StdResult<cw20::BalanceResponse> {
+    assert_snapshot_height(deps, height)?;
+    let address = deps.api.addr_validate(&address)?;
+    let balance = BALANCE_SNAPSHOTS
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+    Ok(cw20::BalanceResponse { balance })
+}
+
+#[cfg(feature = "snapshot")]
+pub fn query_total_supply_at_height(deps: Deps, height: u64) -> StdResult<TotalSupplyResponse> {
+    assert_snapshot_height(deps, height)?;
+    let total_supply = TOTAL_SUPPLY_SNAPSHOTS
+        .may_load_at_height(deps.storage, height)?
+        .unwrap_or_default();
+    Ok(TotalSupplyResponse { total_supply })
+}
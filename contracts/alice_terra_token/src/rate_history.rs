@@ -0,0 +1,109 @@
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult};
+
+use crate::emergency_exit::assert_not_exited;
+use crate::error::ContractError;
+use crate::state::{
+    rate_snapshot_count_mut, rate_snapshot_count_read, rate_snapshots_mut, rate_snapshots_read,
+    RateSnapshot,
+};
+use crate::yield_strategy::load_yield_strategy;
+
+/// Max snapshots kept, 31 days of hourly snapshots
+pub const RATE_HISTORY_CAPACITY: u32 = 31 * 24;
+/// Min seconds between two snapshots
+pub const RATE_RECORD_INTERVAL: u64 = 60 * 60;
+
+pub fn latest_rate_snapshot(deps: Deps) -> StdResult<Option<RateSnapshot>> {
+    rate_snapshots_read(deps.storage)
+        .range(None, None, Order::Descending)
+        .next()
+        .transpose()
+        .map(|item| item.map(|(_, snapshot)| snapshot))
+}
+
+/// Records the Anchor exchange rate if the latest snapshot is older than RATE_RECORD_INTERVAL,
+/// evicting the oldest snapshot once RATE_HISTORY_CAPACITY is reached
+pub fn try_record_rate(deps: DepsMut, env: &Env) -> StdResult<Option<RateSnapshot>> {
+    let time = env.block.time.seconds();
+    if let Some(latest) = latest_rate_snapshot(deps.as_ref())? {
+        if time < latest.time + RATE_RECORD_INTERVAL {
+            return Ok(None);
+        }
+    }
+
+    let snapshot = RateSnapshot {
+        time,
+        height: env.block.height,
+        exchange_rate: load_yield_strategy(deps.storage)?
+            .query_exchange_rate(deps.as_ref(), env.block.height)?,
+    };
+
+    let mut count = rate_snapshot_count_read(deps.storage)
+        .may_load()?
+        .unwrap_or_default();
+    if count >= RATE_HISTORY_CAPACITY {
+        let oldest_key = rate_snapshots_read(deps.storage)
+            .range(None, None, Order::Ascending)
+            .next()
+            .transpose()?
+            .map(|(key, _)| key);
+        if let Some(oldest_key) = oldest_key {
+            rate_snapshots_mut(deps.storage).remove(&oldest_key);
+            count -= 1;
+        }
+    }
+
+    rate_snapshots_mut(deps.storage).save(&time.to_be_bytes(), &snapshot)?;
+    rate_snapshot_count_mut(deps.storage).save(&(count + 1))?;
+
+    Ok(Some(snapshot))
+}
+
+pub fn execute_record_rate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_not_exited(deps.storage)?;
+    let snapshot = try_record_rate(deps, &env)?.ok_or(ContractError::RateRecordedRecently {})?;
+
+    Ok(Response::new()
+        .add_attribute("time", snapshot.time.to_string())
+        .add_attribute("exchange_rate", snapshot.exchange_rate.to_string()))
+}
+
+/// Exchange rate growth from the latest snapshot back to the snapshot at least
+/// `days` older, annualized linearly. None if the history is not long enough
+pub fn trailing_apy(deps: Deps, days: u64) -> StdResult<Option<Decimal256>> {
+    let latest = match latest_rate_snapshot(deps)? {
+        Some(latest) => latest,
+        None => return Ok(None),
+    };
+
+    let start_time = match latest.time.checked_sub(days * 24 * 60 * 60) {
+        Some(start_time) => start_time,
+        None => return Ok(None),
+    };
+    let start = rate_snapshots_read(deps.storage)
+        .range(
+            None,
+            Some(&(start_time + 1).to_be_bytes()),
+            Order::Descending,
+        )
+        .next()
+        .transpose()?
+        .map(|(_, snapshot)| snapshot);
+    let start = match start {
+        Some(start) if !start.exchange_rate.is_zero() => start,
+        _ => return Ok(None),
+    };
+
+    if latest.exchange_rate <= start.exchange_rate {
+        return Ok(Some(Decimal256::zero()));
+    }
+    let growth = (latest.exchange_rate - start.exchange_rate) / start.exchange_rate;
+    Ok(Some(
+        growth * Decimal256::from_ratio(365 * 24 * 60 * 60, latest.time - start.time),
+    ))
+}
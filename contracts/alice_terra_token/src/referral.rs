@@ -0,0 +1,77 @@
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Uint128};
+
+use crate::error::ContractError;
+use crate::snapshot::execute_transfer;
+use crate::state::{
+    referral_counts_mut, referral_earnings_mut, referrals_mut, referrers_mut, referrers_read,
+    Config,
+};
+
+/// Records referrer for user, the first recorded referrer is kept.
+/// Callers only pass referrers chosen by user itself or by the owner or an operator,
+/// so others can't squat its referrer
+pub fn record_referral(
+    deps: DepsMut,
+    user: &Addr,
+    referrer: Option<String>,
+) -> Result<(), ContractError> {
+    let referrer = match referrer {
+        Some(referrer) => deps.api.addr_validate(&referrer)?,
+        None => return Ok(()),
+    };
+    if referrer == *user {
+        return Err(ContractError::InvalidReferrer {});
+    }
+
+    if referrers_read(deps.storage)
+        .may_load(user.as_bytes())?
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    referrers_mut(deps.storage).save(user.as_bytes(), &referrer)?;
+    referrals_mut(deps.storage, &referrer).save(user.as_bytes(), &true)?;
+    referral_counts_mut(deps.storage)
+        .update(referrer.as_bytes(), |count| -> Result<_, ContractError> {
+            Ok(count.unwrap_or_default() + 1)
+        })?;
+    Ok(())
+}
+
+/// Transfers referral_share of fee_amount aliceUST from info.sender to its referrer,
+/// returns the transferred amount
+pub fn transfer_referral_share(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    config: &Config,
+    fee_amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let referrer = match referrers_read(deps.storage).may_load(info.sender.as_bytes())? {
+        Some(referrer) => referrer,
+        None => return Ok(Uint128::zero()),
+    };
+
+    let referral_amount = Uint128::from(config.referral_share * Uint256::from(fee_amount));
+    if referral_amount.is_zero() {
+        return Ok(referral_amount);
+    }
+
+    execute_transfer(
+        deps.branch(),
+        env,
+        info,
+        referrer.to_string(),
+        referral_amount,
+    )?;
+    referral_earnings_mut(deps.storage).update(
+        referrer.as_bytes(),
+        |earnings| -> Result<_, ContractError> {
+            Ok(earnings.unwrap_or_default() + referral_amount)
+        },
+    )?;
+
+    Ok(referral_amount)
+}
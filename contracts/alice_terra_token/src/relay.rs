@@ -7,9 +7,9 @@ use sha2::{Digest, Sha256};
 
 use crate::contract;
 use crate::error::ContractError;
+use crate::fees::transfer_fee;
 use crate::msg::{ExecuteMsg, MetaTx};
 use crate::state::{config_read, nonces_mut};
-use cw20_base::contract::execute_transfer;
 
 pub fn verify_cosmos(
     deps: Deps,
@@ -72,7 +72,32 @@ pub fn execute_relay(
         ExecuteMsg::DepositStable { .. } | ExecuteMsg::DepositStableAuthorized { .. } => {
             Err(ContractError::InvalidRelay {})
         }
+        // Disallow permissionless keeper messages
+        ExecuteMsg::ProcessWithdrawals { .. }
+        | ExecuteMsg::ProcessSchedules { .. }
+        | ExecuteMsg::RecordRate {}
+        | ExecuteMsg::RecountHolders { .. } => Err(ContractError::InvalidRelay {}),
+        // Disallow owner messages
+        ExecuteMsg::UpdateConfig { .. }
+        | ExecuteMsg::ProposeNewOwner { .. }
+        | ExecuteMsg::AcceptOwnership {}
+        | ExecuteMsg::CancelOwnershipProposal {}
+        | ExecuteMsg::UpdateOperators { .. }
+        | ExecuteMsg::UpdateFeeExemptions { .. }
+        | ExecuteMsg::UpdateMarketing { .. }
+        | ExecuteMsg::UploadLogo(_)
+        | ExecuteMsg::SkimSurplus { .. }
+        | ExecuteMsg::RescueCw20 { .. }
+        | ExecuteMsg::RescueNative { .. } => Err(ContractError::InvalidRelay {}),
+        // Disallow guardian messages
+        ExecuteMsg::Pause { .. } | ExecuteMsg::Unpause {} | ExecuteMsg::EmergencyExit {} => {
+            Err(ContractError::InvalidRelay {})
+        }
         ExecuteMsg::RedeemStable { .. }
+        | ExecuteMsg::DepositStableAuthorizedBatch { .. }
+        | ExecuteMsg::CancelWithdrawal { .. }
+        | ExecuteMsg::CreateSchedule { .. }
+        | ExecuteMsg::CancelSchedule { .. }
         | ExecuteMsg::Transfer { .. }
         | ExecuteMsg::Burn { .. }
         | ExecuteMsg::Send { .. } => {
@@ -83,12 +108,12 @@ pub fn execute_relay(
             // Collect tip
             let tip = meta_tx.tip.unwrap_or_default();
             if tip > Uint128::zero() {
-                let owner = config_read(deps.storage).load()?.owner;
-                execute_transfer(
+                let config = config_read(deps.storage).load()?;
+                transfer_fee(
                     deps.branch(),
                     env.clone(),
                     as_user_info.clone(),
-                    owner.to_string(),
+                    &config,
                     tip,
                 )?;
             }
@@ -96,7 +121,7 @@ pub fn execute_relay(
             // Execute msg as user
             let result = contract::execute(deps, env, as_user_info, meta_tx.msg);
             match result {
-                Ok(response) => Ok(response.add_attribute("relay_sender", &human_addr.to_string())),
+                Ok(response) => Ok(response.add_attribute("relay_sender", human_addr.to_string())),
                 Err(err) => {
                     // if tip > 0, collect tip even when relayed msg errors
                     if tip > Uint128::zero() {
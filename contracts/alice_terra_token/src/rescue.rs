@@ -0,0 +1,90 @@
+use cosmwasm_std::{
+    to_binary, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::error::ContractError;
+use crate::state::config_read;
+use crate::utils::deduct_tax;
+
+/// Transfers CW20 tokens mistakenly sent to the contract. aUST & aliceUST are never moved
+pub fn execute_rescue_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token: String,
+    amount: Uint128,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let token = deps.api.addr_validate(&token)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    // aUST backs aliceUST, surplus is moved by SkimSurplus.
+    // aliceUST held by the contract is escrowed for withdrawal tickets
+    if token == config.aterra_token_addr || token == env.contract.address {
+        return Err(ContractError::ProtectedAsset {});
+    }
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+        }))
+        .add_attribute("rescue_token", token)
+        .add_attribute("rescue_amount", amount)
+        .add_attribute("rescue_recipient", recipient))
+}
+
+/// Sends native tokens mistakenly sent to the contract. Stable denom is never moved
+pub fn execute_rescue_native(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    denom: String,
+    amount: Uint128,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    // Stable denom is held between Anchor operations & backs aliceUST after the emergency exit
+    if denom == config.stable_denom {
+        return Err(ContractError::ProtectedAsset {});
+    }
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![deduct_tax(
+                deps.as_ref(),
+                Coin {
+                    denom: denom.clone(),
+                    amount,
+                },
+            )?],
+        }))
+        .add_attribute("rescue_denom", denom)
+        .add_attribute("rescue_amount", amount)
+        .add_attribute("rescue_recipient", recipient))
+}
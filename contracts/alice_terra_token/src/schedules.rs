@@ -0,0 +1,263 @@
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::{
+    coin, Addr, BankMsg, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Timestamp,
+    Uint128,
+};
+use cw0::may_pay;
+
+use crate::authz::query_send_authorization_limit;
+use crate::emergency_exit::assert_not_exited;
+use crate::error::ContractError;
+use crate::execute::{authorized_send_msg, check_deposit_caps, deposit_stable_shares};
+use crate::query::query_native_balance;
+use crate::state::{
+    config_read, next_schedule_id_mut, schedule_owners_mut, schedule_owners_read,
+    schedule_queue_mut, schedule_queue_read, schedules_mut, schedules_read, DepositShare, Schedule,
+};
+use crate::utils::{compute_tax, deduct_tax};
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+/// Minimum seconds between deposits of a schedule
+pub const MIN_SCHEDULE_INTERVAL: u64 = 60 * 60;
+/// Minimum stablecoin amount of each deposit, 1 UST
+pub const MIN_SCHEDULE_AMOUNT: u128 = 1_000_000;
+/// Max schedules per owner
+pub const MAX_SCHEDULES_PER_OWNER: usize = 10;
+
+/// Queue key of a schedule, ordered by due time then id
+fn queue_key(schedule: &Schedule) -> Vec<u8> {
+    [
+        schedule.next_deposit.seconds().to_be_bytes(),
+        schedule.id.to_be_bytes(),
+    ]
+    .concat()
+}
+
+fn remove_schedule(storage: &mut dyn Storage, schedule: &Schedule) {
+    schedules_mut(storage).remove(&schedule.id.to_be_bytes());
+    schedule_owners_mut(storage, &schedule.owner).remove(&schedule.id.to_be_bytes());
+    schedule_queue_mut(storage).remove(&queue_key(schedule));
+}
+
+/// Move a processed schedule to its next due time after now, missed deposits are not caught up.
+/// Removes the schedule once it has ended
+fn advance_schedule(storage: &mut dyn Storage, env: &Env, schedule: &Schedule) -> StdResult<()> {
+    schedule_queue_mut(storage).remove(&queue_key(schedule));
+
+    let elapsed = env
+        .block
+        .time
+        .seconds()
+        .saturating_sub(schedule.next_deposit.seconds());
+    let next_deposit = schedule
+        .next_deposit
+        .plus_seconds((elapsed / schedule.interval + 1) * schedule.interval);
+
+    if matches!(schedule.end, Some(end) if next_deposit > end) {
+        remove_schedule(storage, schedule);
+        return Ok(());
+    }
+
+    let schedule = Schedule {
+        next_deposit,
+        ..schedule.clone()
+    };
+    schedules_mut(storage).save(&schedule.id.to_be_bytes(), &schedule)?;
+    schedule_queue_mut(storage).save(&queue_key(&schedule), &schedule.id)?;
+    Ok(())
+}
+
+/// Deposit amount from sender's SendAuthorization every interval seconds until end,
+/// the first deposit is due immediately
+pub fn execute_create_schedule(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    interval: u64,
+    end: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    if amount < Uint128::from(MIN_SCHEDULE_AMOUNT) {
+        return Err(ContractError::ScheduleAmountTooLow {});
+    }
+    if interval < MIN_SCHEDULE_INTERVAL {
+        return Err(ContractError::ScheduleIntervalTooShort {});
+    }
+    if matches!(end, Some(end) if end <= env.block.time) {
+        return Err(ContractError::Expired {});
+    }
+
+    let schedule_count = schedule_owners_read(deps.storage, &info.sender)
+        .range(None, None, Order::Ascending)
+        .take(MAX_SCHEDULES_PER_OWNER)
+        .count();
+    if schedule_count >= MAX_SCHEDULES_PER_OWNER {
+        return Err(ContractError::TooManySchedules {});
+    }
+
+    let mut next_id = next_schedule_id_mut(deps.storage);
+    let id = next_id.may_load()?.unwrap_or_default();
+    next_id.save(&(id + 1))?;
+
+    let schedule = Schedule {
+        id,
+        owner: info.sender,
+        amount,
+        interval,
+        next_deposit: env.block.time,
+        end,
+    };
+    schedules_mut(deps.storage).save(&id.to_be_bytes(), &schedule)?;
+    schedule_owners_mut(deps.storage, &schedule.owner).save(&id.to_be_bytes(), &true)?;
+    schedule_queue_mut(deps.storage).save(&queue_key(&schedule), &id)?;
+
+    Ok(Response::new()
+        .add_attribute("schedule_id", id.to_string())
+        .add_attribute("amount", amount)
+        .add_attribute("interval", interval.to_string()))
+}
+
+pub fn execute_cancel_schedule(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let schedule = schedules_read(deps.storage)
+        .may_load(&id.to_be_bytes())?
+        .ok_or(ContractError::ScheduleNotFound {})?;
+    if info.sender != schedule.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    remove_schedule(deps.storage, &schedule);
+
+    Ok(Response::new().add_attribute("schedule_id", id.to_string()))
+}
+
+/// Deposit due schedules in a single MsgExec & Anchor deposit. Executable by anyone,
+/// sent funds must pay the tax of each MsgSend and of the Anchor deposit.
+/// Schedules that would fail the batch, without enough SendAuthorization or balance
+/// or over a deposit cap, are skipped until their next interval
+pub fn execute_process_schedules(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    assert_not_exited(deps.storage)?;
+
+    // Take due schedules from the front of the queue
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let due_end = (env.block.time.seconds() + 1).to_be_bytes();
+    let schedules_bucket = schedules_read(deps.storage);
+    let schedules = schedule_queue_read(deps.storage)
+        .range(None, Some(&due_end), Order::Ascending)
+        .take(limit)
+        .map(|item| item.and_then(|(_, id)| schedules_bucket.load(&id.to_be_bytes())))
+        .collect::<StdResult<Vec<_>>>()?;
+    if schedules.is_empty() {
+        return Err(ContractError::NoDueSchedules {});
+    }
+
+    // Sum per owner, an owner may have several due schedules
+    let mut sends: Vec<(Addr, Uint128)> = vec![];
+    let mut shares: Vec<DepositShare> = vec![];
+    let mut skipped_count = 0u32;
+    for schedule in &schedules {
+        let send_index = sends.iter().position(|(owner, _)| *owner == schedule.owner);
+        let sent_amount = send_index.map(|i| sends[i].1).unwrap_or_default();
+        let spend_limit = query_send_authorization_limit(
+            deps.as_ref(),
+            &env,
+            schedule.owner.to_string(),
+            env.contract.address.to_string(),
+            &config.stable_denom,
+        )?;
+        let balance = query_native_balance(
+            deps.as_ref(),
+            schedule.owner.clone(),
+            config.stable_denom.clone(),
+        )?;
+        if spend_limit < sent_amount + schedule.amount || balance < sent_amount + schedule.amount {
+            skipped_count += 1;
+            continue;
+        }
+
+        let share = DepositShare {
+            recipient: schedule.owner.clone(),
+            stable_amount: schedule.amount,
+        };
+        let candidate_shares = [shares.as_slice(), std::slice::from_ref(&share)].concat();
+        match check_deposit_caps(deps.as_ref(), &env, &config, &candidate_shares) {
+            Ok(()) => {}
+            Err(ContractError::TotalDepositCapReached {})
+            | Err(ContractError::AddressDepositCapReached {}) => {
+                skipped_count += 1;
+                continue;
+            }
+            Err(err) => return Err(err),
+        }
+
+        match send_index {
+            Some(i) => sends[i].1 += schedule.amount,
+            None => sends.push((schedule.owner.clone(), schedule.amount)),
+        }
+        shares.push(share);
+    }
+
+    // use received funds for the tax of each MsgSend & of the Anchor deposit
+    let amount: Uint128 = sends.iter().map(|(_, amount)| *amount).sum();
+    let stable_coin = coin(amount.u128(), config.stable_denom.clone());
+    let mut tax_amount = Uint256::zero();
+    if !shares.is_empty() {
+        tax_amount = compute_tax(deps.as_ref(), &stable_coin)?;
+        for (_, send_amount) in &sends {
+            tax_amount += compute_tax(
+                deps.as_ref(),
+                &coin(send_amount.u128(), config.stable_denom.clone()),
+            )?;
+        }
+    }
+    let received_stable_amount = may_pay(&info, config.stable_denom.as_str())?;
+    if Uint256::from(received_stable_amount) < tax_amount {
+        return Err(ContractError::TaxFundsTooLow {});
+    }
+
+    for schedule in &schedules {
+        advance_schedule(deps.storage, &env, schedule)?;
+    }
+
+    let mut response = Response::new()
+        .add_attribute("schedule_count", schedules.len().to_string())
+        .add_attribute("skipped_count", skipped_count.to_string());
+
+    if !shares.is_empty() {
+        let msg_exec = authorized_send_msg(&env, &config.stable_denom, &sends)?;
+        let deposit_res = deposit_stable_shares(deps.branch(), env, shares, stable_coin)?;
+        response = response
+            .add_message(msg_exec)
+            .add_attributes(deposit_res.attributes)
+            .add_submessages(deposit_res.messages);
+    }
+
+    // Refund funds beyond the tax, all of them if every schedule was skipped
+    let refund_amount = received_stable_amount - Uint128::from(tax_amount);
+    if !refund_amount.is_zero() {
+        let refund_coin = deduct_tax(
+            deps.as_ref(),
+            coin(refund_amount.u128(), config.stable_denom),
+        )?;
+        response = response
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![refund_coin],
+            })
+            .add_attribute("refund_amount", refund_amount);
+    }
+
+    Ok(response)
+}
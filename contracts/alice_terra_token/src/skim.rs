@@ -0,0 +1,49 @@
+use cosmwasm_std::{to_binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use cw20_base::state::TOKEN_INFO;
+
+use crate::error::ContractError;
+use crate::state::{config_read, skimmed_total_mut};
+use crate::yield_strategy::yield_strategy;
+
+/// Transfers aUST held beyond the aliceUST supply to recipient.
+/// aUST backing the supply is never moved
+pub fn execute_skim_surplus(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+    let aterra_balance =
+        yield_strategy(&config).query_position(deps.as_ref(), env.contract.address)?;
+    let surplus = aterra_balance.saturating_sub(total_supply);
+    if surplus.is_zero() {
+        return Err(ContractError::NoSurplus {});
+    }
+
+    let mut skimmed_total = skimmed_total_mut(deps.storage);
+    let total = skimmed_total.may_load()?.unwrap_or_default() + surplus;
+    skimmed_total.save(&total)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.aterra_token_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: surplus,
+            })?,
+        }))
+        .add_attribute("skim_amount", surplus)
+        .add_attribute("skim_recipient", recipient)
+        .add_attribute("skimmed_total", total))
+}
@@ -0,0 +1,150 @@
+//! cw20-base balance operations that also maintain the holder count and, when the
+//! `snapshot` feature is enabled, record balance & total supply snapshots
+
+use cosmwasm_std::{
+    Addr, Binary, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128,
+};
+use cw20_base::contract;
+
+use crate::enumerable::{holders_before_change, update_holder_count};
+use crate::error::ContractError;
+
+#[cfg(feature = "snapshot")]
+use cw20_base::state::{BALANCES, TOKEN_INFO};
+
+#[cfg(feature = "snapshot")]
+use crate::state::{BALANCE_SNAPSHOTS, SNAPSHOT_START_HEIGHT, TOTAL_SUPPLY_SNAPSHOTS};
+
+/// Starts snapshots at height, if not started yet
+#[cfg(feature = "snapshot")]
+pub fn init_snapshots(storage: &mut dyn Storage, height: u64) -> StdResult<()> {
+    if SNAPSHOT_START_HEIGHT.may_load(storage)?.is_some() {
+        return Ok(());
+    }
+    SNAPSHOT_START_HEIGHT.save(storage, &height)?;
+
+    // Balance snapshots share the cw20 balances namespace, only total supply needs seeding.
+    // Seed at the previous height so the changelog entry predates every queryable height
+    let total_supply = TOKEN_INFO.load(storage)?.total_supply;
+    TOTAL_SUPPLY_SNAPSHOTS.save(storage, &total_supply, height.saturating_sub(1))
+}
+
+/// Records the balances of addresses before they change at height
+#[cfg(feature = "snapshot")]
+fn checkpoint_balances(
+    storage: &mut dyn Storage,
+    height: u64,
+    addresses: &[&Addr],
+) -> StdResult<()> {
+    for address in addresses {
+        match BALANCES.may_load(storage, address)? {
+            Some(balance) => BALANCE_SNAPSHOTS.save(storage, address, &balance, height)?,
+            None => BALANCE_SNAPSHOTS.remove(storage, address, height)?,
+        }
+    }
+    Ok(())
+}
+
+/// Records the total supply after it changed at height
+#[cfg(feature = "snapshot")]
+fn checkpoint_total_supply(storage: &mut dyn Storage, height: u64) -> StdResult<()> {
+    let total_supply = TOKEN_INFO.load(storage)?.total_supply;
+    TOTAL_SUPPLY_SNAPSHOTS.save(storage, &total_supply, height)
+}
+
+/// Returns whether addresses hold aliceUST before their balances change at height
+#[cfg_attr(not(feature = "snapshot"), allow(unused_variables))]
+fn before_balance_change(
+    storage: &mut dyn Storage,
+    height: u64,
+    addresses: &[&Addr],
+) -> StdResult<Vec<bool>> {
+    #[cfg(feature = "snapshot")]
+    checkpoint_balances(storage, height, addresses)?;
+
+    holders_before_change(storage, addresses)
+}
+
+pub fn execute_transfer(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let sender = info.sender.clone();
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    let addresses = if sender == recipient_addr {
+        vec![&sender]
+    } else {
+        vec![&sender, &recipient_addr]
+    };
+    let were_holders = before_balance_change(deps.storage, env.block.height, &addresses)?;
+
+    let response = contract::execute_transfer(deps.branch(), env, info, recipient, amount)?;
+
+    update_holder_count(deps.storage, &addresses, were_holders)?;
+    Ok(response)
+}
+
+pub fn execute_send(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let sender = info.sender.clone();
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    let addresses = if sender == contract_addr {
+        vec![&sender]
+    } else {
+        vec![&sender, &contract_addr]
+    };
+    let were_holders = before_balance_change(deps.storage, env.block.height, &addresses)?;
+
+    let response = contract::execute_send(deps.branch(), env, info, contract, amount, msg)?;
+
+    update_holder_count(deps.storage, &addresses, were_holders)?;
+    Ok(response)
+}
+
+pub fn execute_mint(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let height = env.block.height;
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    let were_holders = before_balance_change(deps.storage, height, &[&recipient_addr])?;
+
+    let response = contract::execute_mint(deps.branch(), env, info, recipient, amount)?;
+
+    update_holder_count(deps.storage, &[&recipient_addr], were_holders)?;
+    #[cfg(feature = "snapshot")]
+    checkpoint_total_supply(deps.storage, height)?;
+
+    Ok(response)
+}
+
+pub fn execute_burn(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let height = env.block.height;
+    let sender = info.sender.clone();
+    let were_holders = before_balance_change(deps.storage, height, &[&sender])?;
+
+    let response = contract::execute_burn(deps.branch(), env, info, amount)?;
+
+    update_holder_count(deps.storage, &[&sender], were_holders)?;
+    #[cfg(feature = "snapshot")]
+    checkpoint_total_supply(deps.storage, height)?;
+
+    Ok(response)
+}
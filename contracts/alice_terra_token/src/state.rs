@@ -1,22 +1,84 @@
 use cosmwasm_bignumber::Decimal256;
-use cosmwasm_std::{Addr, StdError, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, Coin, StdError, StdResult, Storage, Timestamp, Uint128};
 use cosmwasm_storage::{
     bucket, bucket_read, singleton, singleton_read, Bucket, ReadonlyBucket, ReadonlySingleton,
     Singleton,
 };
+use cw0::Expiration;
+use cw_storage_plus::Map;
+#[cfg(feature = "snapshot")]
+use cw_storage_plus::{Item, SnapshotItem, SnapshotMap, Strategy};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub static CONFIG_KEY: &[u8] = b"config";
 pub static PENDING_REDEEM_STABLE_KEY: &[u8] = b"pending_redeem_stable";
 pub static PENDING_DEPOSIT_STABLE_KEY: &[u8] = b"pending_deposit_stable";
+pub static PENDING_SWAP_DEPOSIT_KEY: &[u8] = b"pending_swap_deposit";
+pub static PENDING_SWAP_REDEEM_KEY: &[u8] = b"pending_swap_redeem";
 pub static NONCE_KEY: &[u8] = b"nonce";
+pub static WITHDRAWAL_TICKETS_KEY: &[u8] = b"withdrawal_tickets";
+pub static WITHDRAWAL_TICKET_OWNERS_KEY: &[u8] = b"withdrawal_ticket_owners";
+pub static NEXT_WITHDRAWAL_TICKET_ID_KEY: &[u8] = b"next_withdrawal_ticket_id";
+pub static PENDING_PROCESS_WITHDRAWALS_KEY: &[u8] = b"pending_process_withdrawals";
+pub static FEE_EXEMPTIONS_KEY: &[u8] = b"fee_exemptions";
+pub static REFERRERS_KEY: &[u8] = b"referrers";
+pub static REFERRALS_KEY: &[u8] = b"referrals";
+pub static REFERRAL_EARNINGS_KEY: &[u8] = b"referral_earnings";
+pub static REFERRAL_COUNTS_KEY: &[u8] = b"referral_counts";
+pub static RATE_SNAPSHOTS_KEY: &[u8] = b"rate_snapshots";
+pub static RATE_SNAPSHOT_COUNT_KEY: &[u8] = b"rate_snapshot_count";
+pub static HOLDER_COUNT_KEY: &[u8] = b"holder_count";
+pub static HOLDER_RECOUNT_KEY: &[u8] = b"holder_recount";
+pub static SKIMMED_TOTAL_KEY: &[u8] = b"skimmed_total";
+pub static PAUSE_STATE_KEY: &[u8] = b"pause_state";
+pub static PENDING_EMERGENCY_EXIT_KEY: &[u8] = b"pending_emergency_exit";
+pub static EMERGENCY_EXIT_KEY: &[u8] = b"emergency_exit";
+pub static PENDING_OWNER_KEY: &[u8] = b"pending_owner";
+pub static OPERATORS_KEY: &[u8] = b"operators";
+pub static SCHEDULES_KEY: &[u8] = b"schedules";
+pub static SCHEDULE_OWNERS_KEY: &[u8] = b"schedule_owners";
+pub static SCHEDULE_QUEUE_KEY: &[u8] = b"schedule_queue";
+pub static NEXT_SCHEDULE_ID_KEY: &[u8] = b"next_schedule_id";
+
+/// UST principal of each account, stored next to the cw20 balances
+pub const PRINCIPALS: Map<&Addr, Principal> = Map::new("principal");
+
+/// Snapshots of the cw20 balances, the primary namespace is shared with cw20-base BALANCES
+#[cfg(feature = "snapshot")]
+pub const BALANCE_SNAPSHOTS: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "balance",
+    "balance__checkpoints",
+    "balance__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Snapshots of the cw20 total supply, mirrors cw20-base TOKEN_INFO.total_supply
+#[cfg(feature = "snapshot")]
+pub const TOTAL_SUPPLY_SNAPSHOTS: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_supply",
+    "total_supply__checkpoints",
+    "total_supply__changelog",
+    Strategy::EveryBlock,
+);
+
+/// First height with snapshots
+#[cfg(feature = "snapshot")]
+pub const SNAPSHOT_START_HEIGHT: Item<u64> = Item::new("snapshot_start_height");
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Principal {
+    /// UST deposited & value of aliceUST transferred in
+    pub deposited: Uint128,
+    /// UST redeemed & value of aliceUST transferred out
+    pub withdrawn: Uint128,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     /// stablecoin denomination, probably `uusd`
     pub stable_denom: String,
-    /// account that collects Anchor & relay fees
+    /// account that collects Anchor & relay fees when fee_recipients is empty
     pub owner: Addr,
     /// Anchor Money Market Contract address
     pub money_market_addr: Addr,
@@ -24,9 +86,69 @@ pub struct Config {
     pub aterra_token_addr: Addr,
     /// Redeem fee ratio between 0 and 1
     pub redeem_fee_ratio: Decimal256,
+    /// Terra native denoms accepted for deposit, swapped to stable_denom through the market module
+    pub swap_denoms: Vec<String>,
+    /// Max aliceUST balance an address can reach by deposits, none is unlimited.
+    /// Soft limit, holders can transfer out & deposit again
+    pub address_deposit_cap: Option<Uint128>,
+    /// Max total aliceUST supply reachable by deposits, none is unlimited.
+    /// Deposit fees minted on top of the deposits are not counted
+    pub total_deposit_cap: Option<Uint128>,
+    /// Deposit fee ratio between 0 and 1
+    pub deposit_fee_ratio: Decimal256,
+    /// Accounts that split redeem fees, deposit fees & relay tips. Empty means all to owner
+    pub fee_recipients: Vec<FeeRecipient>,
+    /// Redeem fee ratios by burn amount, sorted by min_amount. Below the first tier redeem_fee_ratio applies
+    pub redeem_fee_tiers: Vec<RedeemFeeTier>,
+    /// Share of redeem fees & relay tips paid by referred users that goes to their referrer
+    pub referral_share: Decimal256,
+    /// account that can pause & unpause the contract, none disables pausing
+    pub guardian: Option<Addr>,
+    /// tax charged on native transfers
+    pub tax_policy: TaxPolicy,
+    /// money market holding the deposited stablecoins
+    pub yield_backend: YieldBackend,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum YieldBackend {
+    /// Anchor money market at money_market_addr, positions held in aTerra at aterra_token_addr
+    #[default]
+    Anchor,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxPolicy {
+    /// Tax rate & cap queried from the Terra treasury module
+    #[default]
+    Terra,
+    /// Fixed tax rate between 0 and 1, capped per transfer if cap is set
+    Fixed {
+        rate: Decimal256,
+        cap: Option<Uint128>,
+    },
+    /// No tax, for chains without the Terra treasury module
+    None,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RedeemFeeTier {
+    /// Minimum burn amount in aliceUST for this tier
+    pub min_amount: Uint128,
+    /// Redeem fee ratio between 0 and 1
+    pub fee_ratio: Decimal256,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeRecipient {
+    pub address: Addr,
+    /// Share of each fee between 0 and 1, shares of all recipients sum to 1
+    pub share: Decimal256,
 }
 
-fn config_mut(storage: &mut dyn Storage) -> Singleton<Config> {
+fn config_mut(storage: &mut dyn Storage) -> Singleton<'_, Config> {
     singleton(storage, CONFIG_KEY)
 }
 
@@ -37,19 +159,133 @@ pub fn save_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()>
         ));
     }
 
+    if config.deposit_fee_ratio > Decimal256::one() {
+        return Err(StdError::generic_err(
+            "deposit_fee_ratio must be between 0 and 1",
+        ));
+    }
+
+    if let TaxPolicy::Fixed { rate, .. } = config.tax_policy {
+        if rate > Decimal256::one() {
+            return Err(StdError::generic_err(
+                "tax_policy rate must be between 0 and 1",
+            ));
+        }
+    }
+
+    if config.referral_share > Decimal256::one() {
+        return Err(StdError::generic_err(
+            "referral_share must be between 0 and 1",
+        ));
+    }
+
+    if !config.fee_recipients.is_empty() {
+        let total_share = config
+            .fee_recipients
+            .iter()
+            .fold(Decimal256::zero(), |total, recipient| {
+                total + recipient.share
+            });
+        if total_share != Decimal256::one() {
+            return Err(StdError::generic_err("fee_recipients shares must sum to 1"));
+        }
+    }
+
+    for (i, tier) in config.redeem_fee_tiers.iter().enumerate() {
+        if tier.fee_ratio > Decimal256::one() {
+            return Err(StdError::generic_err(
+                "redeem_fee_tiers fee_ratio must be between 0 and 1",
+            ));
+        }
+        if i > 0 && tier.min_amount <= config.redeem_fee_tiers[i - 1].min_amount {
+            return Err(StdError::generic_err(
+                "redeem_fee_tiers must be sorted by ascending min_amount",
+            ));
+        }
+    }
+
+    if config.swap_denoms.contains(&config.stable_denom) {
+        return Err(StdError::generic_err(
+            "swap_denoms must not contain stable_denom",
+        ));
+    }
+
     config_mut(storage).save(config)?;
     Ok(())
 }
 
-pub fn config_read(storage: &dyn Storage) -> ReadonlySingleton<Config> {
+pub fn config_read(storage: &dyn Storage) -> ReadonlySingleton<'_, Config> {
     singleton_read(storage, CONFIG_KEY)
 }
 
-pub fn nonces_mut(storage: &mut dyn Storage) -> Bucket<Uint128> {
+pub fn fee_exemptions_mut(storage: &mut dyn Storage) -> Bucket<'_, bool> {
+    bucket(storage, FEE_EXEMPTIONS_KEY)
+}
+
+pub fn fee_exemptions_read(storage: &dyn Storage) -> ReadonlyBucket<'_, bool> {
+    bucket_read(storage, FEE_EXEMPTIONS_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Operator {
+    /// Max stablecoin amount deposited by DepositStableAuthorized per day, none is unlimited
+    pub daily_limit: Option<Uint128>,
+    /// Day of the last deposit, in days since the unix epoch
+    pub day: u64,
+    /// Stablecoin amount deposited on day
+    pub used: Uint128,
+}
+
+/// Accounts allowed to call DepositStableAuthorized besides owner
+pub fn operators_mut(storage: &mut dyn Storage) -> Bucket<'_, Operator> {
+    bucket(storage, OPERATORS_KEY)
+}
+
+pub fn operators_read(storage: &dyn Storage) -> ReadonlyBucket<'_, Operator> {
+    bucket_read(storage, OPERATORS_KEY)
+}
+
+/// Referrer of each referred user
+pub fn referrers_mut(storage: &mut dyn Storage) -> Bucket<'_, Addr> {
+    bucket(storage, REFERRERS_KEY)
+}
+
+pub fn referrers_read(storage: &dyn Storage) -> ReadonlyBucket<'_, Addr> {
+    bucket_read(storage, REFERRERS_KEY)
+}
+
+/// Users referred by referrer
+pub fn referrals_mut<'a>(storage: &'a mut dyn Storage, referrer: &Addr) -> Bucket<'a, bool> {
+    Bucket::multilevel(storage, &[REFERRALS_KEY, referrer.as_bytes()])
+}
+
+pub fn referrals_read<'a>(storage: &'a dyn Storage, referrer: &Addr) -> ReadonlyBucket<'a, bool> {
+    ReadonlyBucket::multilevel(storage, &[REFERRALS_KEY, referrer.as_bytes()])
+}
+
+/// Number of users referred by each referrer
+pub fn referral_counts_mut(storage: &mut dyn Storage) -> Bucket<'_, u64> {
+    bucket(storage, REFERRAL_COUNTS_KEY)
+}
+
+pub fn referral_counts_read(storage: &dyn Storage) -> ReadonlyBucket<'_, u64> {
+    bucket_read(storage, REFERRAL_COUNTS_KEY)
+}
+
+/// Total aliceUST earned by each referrer
+pub fn referral_earnings_mut(storage: &mut dyn Storage) -> Bucket<'_, Uint128> {
+    bucket(storage, REFERRAL_EARNINGS_KEY)
+}
+
+pub fn referral_earnings_read(storage: &dyn Storage) -> ReadonlyBucket<'_, Uint128> {
+    bucket_read(storage, REFERRAL_EARNINGS_KEY)
+}
+
+pub fn nonces_mut(storage: &mut dyn Storage) -> Bucket<'_, Uint128> {
     bucket(storage, NONCE_KEY)
 }
 
-pub fn nonces_read(storage: &dyn Storage) -> ReadonlyBucket<Uint128> {
+pub fn nonces_read(storage: &dyn Storage) -> ReadonlyBucket<'_, Uint128> {
     bucket_read(storage, NONCE_KEY)
 }
 
@@ -59,29 +295,330 @@ pub struct PendingRedeemStable {
     pub sender: Addr,
     pub recipient: Addr,
     pub burn_amount: Uint128,
+    /// Escrow burn_amount into a withdrawal ticket if Anchor can't redeem
+    pub queue_if_illiquid: bool,
+    /// Swap redeemed stablecoins to this denom before sending
+    pub target_denom: Option<String>,
+    /// minimum target amount the swap must return
+    pub min_target_amount: Option<Uint128>,
 }
 
-pub fn pending_redeem_stable_mut(storage: &mut dyn Storage) -> Singleton<PendingRedeemStable> {
+pub fn pending_redeem_stable_mut(storage: &mut dyn Storage) -> Singleton<'_, PendingRedeemStable> {
     singleton(storage, PENDING_REDEEM_STABLE_KEY)
 }
 
-pub fn pending_redeem_stable_read(storage: &dyn Storage) -> ReadonlySingleton<PendingRedeemStable> {
+pub fn pending_redeem_stable_read(
+    storage: &dyn Storage,
+) -> ReadonlySingleton<'_, PendingRedeemStable> {
     singleton_read(storage, PENDING_REDEEM_STABLE_KEY)
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct PendingDepositStable {
-    pub prev_aterra_balance: Uint128,
+pub struct DepositShare {
     pub recipient: Addr,
+    /// stablecoin amount deposited for recipient
     pub stable_amount: Uint128,
 }
 
-pub fn pending_deposit_stable_mut(storage: &mut dyn Storage) -> Singleton<PendingDepositStable> {
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingDepositStable {
+    pub prev_aterra_balance: Uint128,
+    /// recipients of a single Anchor deposit, minted aliceUST is split by stable_amount
+    pub shares: Vec<DepositShare>,
+    /// coin originally received, before any swap & tax
+    pub deposit_coin: Coin,
+}
+
+pub fn pending_deposit_stable_mut(
+    storage: &mut dyn Storage,
+) -> Singleton<'_, PendingDepositStable> {
     singleton(storage, PENDING_DEPOSIT_STABLE_KEY)
 }
 
 pub fn pending_deposit_stable_read(
     storage: &dyn Storage,
-) -> ReadonlySingleton<PendingDepositStable> {
+) -> ReadonlySingleton<'_, PendingDepositStable> {
     singleton_read(storage, PENDING_DEPOSIT_STABLE_KEY)
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingSwapDeposit {
+    pub prev_stable_balance: Uint128,
+    pub recipient: Addr,
+    /// coin received & offered to the market swap
+    pub offer_coin: Coin,
+    /// minimum stable amount the swap must return
+    pub min_stable_amount: Option<Uint128>,
+}
+
+pub fn pending_swap_deposit_mut(storage: &mut dyn Storage) -> Singleton<'_, PendingSwapDeposit> {
+    singleton(storage, PENDING_SWAP_DEPOSIT_KEY)
+}
+
+pub fn pending_swap_deposit_read(
+    storage: &dyn Storage,
+) -> ReadonlySingleton<'_, PendingSwapDeposit> {
+    singleton_read(storage, PENDING_SWAP_DEPOSIT_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingSwapRedeem {
+    pub prev_target_balance: Uint128,
+    pub recipient: Addr,
+    pub target_denom: String,
+    /// minimum target amount the swap must return
+    pub min_target_amount: Option<Uint128>,
+}
+
+pub fn pending_swap_redeem_mut(storage: &mut dyn Storage) -> Singleton<'_, PendingSwapRedeem> {
+    singleton(storage, PENDING_SWAP_REDEEM_KEY)
+}
+
+pub fn pending_swap_redeem_read(storage: &dyn Storage) -> ReadonlySingleton<'_, PendingSwapRedeem> {
+    singleton_read(storage, PENDING_SWAP_REDEEM_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalTicket {
+    pub id: u64,
+    /// account that created the ticket, may cancel it
+    pub owner: Addr,
+    /// account that receives the stablecoins
+    pub recipient: Addr,
+    /// aliceUST escrowed by the contract, burned when the ticket is fulfilled
+    pub burn_amount: Uint128,
+    /// block time the ticket was created
+    pub created_at: Timestamp,
+}
+
+/// Tickets keyed by big-endian id, so ascending order is FIFO order
+pub fn withdrawal_tickets_mut(storage: &mut dyn Storage) -> Bucket<'_, WithdrawalTicket> {
+    bucket(storage, WITHDRAWAL_TICKETS_KEY)
+}
+
+pub fn withdrawal_tickets_read(storage: &dyn Storage) -> ReadonlyBucket<'_, WithdrawalTicket> {
+    bucket_read(storage, WITHDRAWAL_TICKETS_KEY)
+}
+
+/// Index of ticket ids by owner
+pub fn withdrawal_ticket_owners_mut<'a>(
+    storage: &'a mut dyn Storage,
+    owner: &Addr,
+) -> Bucket<'a, bool> {
+    Bucket::multilevel(storage, &[WITHDRAWAL_TICKET_OWNERS_KEY, owner.as_bytes()])
+}
+
+pub fn withdrawal_ticket_owners_read<'a>(
+    storage: &'a dyn Storage,
+    owner: &Addr,
+) -> ReadonlyBucket<'a, bool> {
+    ReadonlyBucket::multilevel(storage, &[WITHDRAWAL_TICKET_OWNERS_KEY, owner.as_bytes()])
+}
+
+pub fn next_withdrawal_ticket_id_mut(storage: &mut dyn Storage) -> Singleton<'_, u64> {
+    singleton(storage, NEXT_WITHDRAWAL_TICKET_ID_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingProcessWithdrawals {
+    pub prev_stable_balance: Uint128,
+    /// tickets fulfilled by this batch, in FIFO order
+    pub ticket_ids: Vec<u64>,
+}
+
+pub fn pending_process_withdrawals_mut(
+    storage: &mut dyn Storage,
+) -> Singleton<'_, PendingProcessWithdrawals> {
+    singleton(storage, PENDING_PROCESS_WITHDRAWALS_KEY)
+}
+
+pub fn pending_process_withdrawals_read(
+    storage: &dyn Storage,
+) -> ReadonlySingleton<'_, PendingProcessWithdrawals> {
+    singleton_read(storage, PENDING_PROCESS_WITHDRAWALS_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Schedule {
+    pub id: u64,
+    /// account whose SendAuthorization funds the deposits, receives the aliceUST
+    pub owner: Addr,
+    /// stablecoin amount deposited each interval
+    pub amount: Uint128,
+    /// seconds between deposits
+    pub interval: u64,
+    /// block time the next deposit is due
+    pub next_deposit: Timestamp,
+    /// no deposits are made after end
+    pub end: Option<Timestamp>,
+}
+
+/// Schedules keyed by big-endian id
+pub fn schedules_mut(storage: &mut dyn Storage) -> Bucket<'_, Schedule> {
+    bucket(storage, SCHEDULES_KEY)
+}
+
+pub fn schedules_read(storage: &dyn Storage) -> ReadonlyBucket<'_, Schedule> {
+    bucket_read(storage, SCHEDULES_KEY)
+}
+
+/// Index of schedule ids by owner
+pub fn schedule_owners_mut<'a>(storage: &'a mut dyn Storage, owner: &Addr) -> Bucket<'a, bool> {
+    Bucket::multilevel(storage, &[SCHEDULE_OWNERS_KEY, owner.as_bytes()])
+}
+
+pub fn schedule_owners_read<'a>(
+    storage: &'a dyn Storage,
+    owner: &Addr,
+) -> ReadonlyBucket<'a, bool> {
+    ReadonlyBucket::multilevel(storage, &[SCHEDULE_OWNERS_KEY, owner.as_bytes()])
+}
+
+/// Schedule ids keyed by big-endian next deposit time & id, so ascending order is due order
+pub fn schedule_queue_mut(storage: &mut dyn Storage) -> Bucket<'_, u64> {
+    bucket(storage, SCHEDULE_QUEUE_KEY)
+}
+
+pub fn schedule_queue_read(storage: &dyn Storage) -> ReadonlyBucket<'_, u64> {
+    bucket_read(storage, SCHEDULE_QUEUE_KEY)
+}
+
+pub fn next_schedule_id_mut(storage: &mut dyn Storage) -> Singleton<'_, u64> {
+    singleton(storage, NEXT_SCHEDULE_ID_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateSnapshot {
+    /// Block time in seconds
+    pub time: u64,
+    pub height: u64,
+    /// Anchor UST/aTerra exchange rate
+    pub exchange_rate: Decimal256,
+}
+
+/// Exchange rate snapshots keyed by block time
+pub fn rate_snapshots_mut(storage: &mut dyn Storage) -> Bucket<'_, RateSnapshot> {
+    bucket(storage, RATE_SNAPSHOTS_KEY)
+}
+
+pub fn rate_snapshots_read(storage: &dyn Storage) -> ReadonlyBucket<'_, RateSnapshot> {
+    bucket_read(storage, RATE_SNAPSHOTS_KEY)
+}
+
+pub fn rate_snapshot_count_mut(storage: &mut dyn Storage) -> Singleton<'_, u32> {
+    singleton(storage, RATE_SNAPSHOT_COUNT_KEY)
+}
+
+pub fn rate_snapshot_count_read(storage: &dyn Storage) -> ReadonlySingleton<'_, u32> {
+    singleton_read(storage, RATE_SNAPSHOT_COUNT_KEY)
+}
+
+/// Number of addresses with a nonzero aliceUST balance
+pub fn holder_count_mut(storage: &mut dyn Storage) -> Singleton<'_, u64> {
+    singleton(storage, HOLDER_COUNT_KEY)
+}
+
+pub fn holder_count_read(storage: &dyn Storage) -> ReadonlySingleton<'_, u64> {
+    singleton_read(storage, HOLDER_COUNT_KEY)
+}
+
+/// Recount of the holders after migrating from a version without the holder count.
+/// The holder count is unset until the recount is done
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HolderRecount {
+    /// Last address counted, balances are counted in address order
+    pub start_after: Option<Addr>,
+    /// Holders up to start_after
+    pub holder_count: u64,
+}
+
+pub fn holder_recount_mut(storage: &mut dyn Storage) -> Singleton<'_, HolderRecount> {
+    singleton(storage, HOLDER_RECOUNT_KEY)
+}
+
+pub fn holder_recount_read(storage: &dyn Storage) -> ReadonlySingleton<'_, HolderRecount> {
+    singleton_read(storage, HOLDER_RECOUNT_KEY)
+}
+
+/// Total surplus aUST skimmed to the owner's recipients
+pub fn skimmed_total_mut(storage: &mut dyn Storage) -> Singleton<'_, Uint128> {
+    singleton(storage, SKIMMED_TOTAL_KEY)
+}
+
+pub fn skimmed_total_read(storage: &dyn Storage) -> ReadonlySingleton<'_, Uint128> {
+    singleton_read(storage, SKIMMED_TOTAL_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PauseState {
+    /// DepositStable, DepositStableAuthorized, DepositStableAuthorizedBatch & ProcessSchedules
+    pub deposits: bool,
+    /// RedeemStable, Burn & ProcessWithdrawals
+    pub redeems: bool,
+    /// Transfer, Send & CancelWithdrawal
+    pub transfers: bool,
+    /// Relay
+    pub relay: bool,
+}
+
+pub fn pause_state_mut(storage: &mut dyn Storage) -> Singleton<'_, PauseState> {
+    singleton(storage, PAUSE_STATE_KEY)
+}
+
+pub fn pause_state_read(storage: &dyn Storage) -> ReadonlySingleton<'_, PauseState> {
+    singleton_read(storage, PAUSE_STATE_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingEmergencyExit {
+    pub prev_stable_balance: Uint128,
+    /// all aUST held, redeemed from Anchor
+    pub aterra_amount: Uint128,
+}
+
+pub fn pending_emergency_exit_mut(
+    storage: &mut dyn Storage,
+) -> Singleton<'_, PendingEmergencyExit> {
+    singleton(storage, PENDING_EMERGENCY_EXIT_KEY)
+}
+
+pub fn pending_emergency_exit_read(
+    storage: &dyn Storage,
+) -> ReadonlySingleton<'_, PendingEmergencyExit> {
+    singleton_read(storage, PENDING_EMERGENCY_EXIT_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmergencyExit {
+    pub height: u64,
+    pub time: Timestamp,
+    /// aUST redeemed from Anchor
+    pub aterra_amount: Uint128,
+    /// UST received from Anchor
+    pub stable_amount: Uint128,
+}
+
+/// Set once all aUST is redeemed, aliceUST is then backed by the UST held
+pub fn emergency_exit_mut(storage: &mut dyn Storage) -> Singleton<'_, EmergencyExit> {
+    singleton(storage, EMERGENCY_EXIT_KEY)
+}
+
+pub fn emergency_exit_read(storage: &dyn Storage) -> ReadonlySingleton<'_, EmergencyExit> {
+    singleton_read(storage, EMERGENCY_EXIT_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingOwner {
+    /// account proposed as new owner
+    pub owner: Addr,
+    /// proposal can't be accepted after this
+    pub expires: Expiration,
+}
+
+pub fn pending_owner_mut(storage: &mut dyn Storage) -> Singleton<'_, PendingOwner> {
+    singleton(storage, PENDING_OWNER_KEY)
+}
+
+pub fn pending_owner_read(storage: &dyn Storage) -> ReadonlySingleton<'_, PendingOwner> {
+    singleton_read(storage, PENDING_OWNER_KEY)
+}
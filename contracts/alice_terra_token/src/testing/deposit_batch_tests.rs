@@ -0,0 +1,155 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    coins, to_binary, CosmosMsg, Decimal, OwnedDeps, Response, SubMsg, Uint128, WasmMsg,
+};
+use std::str::FromStr;
+
+use crate::anchor::MarketExecuteMsg;
+use crate::contract::{execute, reply};
+use crate::error::ContractError;
+use crate::execute::DEPOSIT_STABLE_REPLY_ID;
+use crate::msg::{AuthorizedDepositMsg, ExecuteMsg, OperatorMsg};
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{
+    instantiate_contract, mock_dependencies, query_balance, set_aterra_balance, OK_DEPOSIT_REPLY,
+};
+
+fn setup(grant: u128) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    // Tax rate: 0.3%, cap of 1 UST
+    deps.querier.with_tax(
+        Decimal::from_str("0.003").unwrap(),
+        &[(&"uusd".to_string(), &Uint128::from(1_000_000_u64))],
+    );
+    deps.querier
+        .with_send_authorizations(&[("employer", &[("uusd", grant)])]);
+    deps
+}
+
+fn deposit_batch(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    tax_funds: u128,
+    deposits: &[(&str, u64)],
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("employer", &coins(tax_funds, "uusd")),
+        ExecuteMsg::DepositStableAuthorizedBatch {
+            granter: "employer".to_string(),
+            deposits: deposits
+                .iter()
+                .map(|(recipient, amount)| AuthorizedDepositMsg {
+                    recipient: recipient.to_string(),
+                    amount: Uint128::from(*amount),
+                })
+                .collect(),
+        },
+    )
+}
+
+#[test]
+fn deposit_batch_splits_minted_amount() {
+    let mut deps = setup(300_000_000);
+
+    // 2*tax on the 300 UST total
+    let res = deposit_batch(
+        &mut deps,
+        1_800_000,
+        &[("user1", 100_000_000), ("user2", 200_000_000)],
+    )
+    .unwrap();
+    assert_eq!(2, res.messages.len());
+    assert!(matches!(
+        &res.messages[0].msg,
+        CosmosMsg::Stargate { type_url, .. } if type_url == "/cosmos.authz.v1beta1.MsgExec"
+    ));
+    assert_eq!(
+        res.messages[1],
+        SubMsg::reply_always(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "money_market_addr".to_string(),
+                funds: coins(300_000_000, "uusd"),
+                msg: to_binary(&MarketExecuteMsg::DepositStable {}).unwrap(),
+            }),
+            DEPOSIT_STABLE_REPLY_ID
+        )
+    );
+
+    // Anchor mints 100,000,001 aUST, last recipient receives the rounding remainder
+    set_aterra_balance(&mut deps, 100_000_001);
+    reply(deps.as_mut(), mock_env(), OK_DEPOSIT_REPLY).unwrap();
+
+    assert_eq!(Uint128::from(33_333_333_u64), query_balance(&deps, "user1"));
+    assert_eq!(Uint128::from(66_666_668_u64), query_balance(&deps, "user2"));
+    assert_eq!(Uint128::zero(), query_balance(&deps, "employer"));
+}
+
+#[test]
+fn deposit_batch_checks_total() {
+    let mut deps = setup(299_999_999);
+    let deposits = [("user1", 100_000_000), ("user2", 200_000_000)];
+
+    // Grant covers each deposit, but not the total
+    let err = deposit_batch(&mut deps, 1_800_000, &deposits).unwrap_err();
+    assert_eq!(ContractError::InsufficientSendAuthorization {}, err);
+
+    // Funds cover 2*tax of each deposit, but not of the total
+    let mut deps = setup(300_000_000);
+    let err = deposit_batch(&mut deps, 1_799_999, &deposits).unwrap_err();
+    assert_eq!(ContractError::TaxFundsTooLow {}, err);
+}
+
+#[test]
+fn deposit_batch_rejects_empty_and_zero_deposits() {
+    let mut deps = setup(300_000_000);
+
+    let err = deposit_batch(&mut deps, 1_800_000, &[]).unwrap_err();
+    assert_eq!(ContractError::EmptyDepositBatch {}, err);
+
+    let err = deposit_batch(
+        &mut deps,
+        1_800_000,
+        &[("user1", 100_000_000), ("user2", 0)],
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::InvalidZeroAmount {}, err);
+}
+
+#[test]
+fn deposit_batch_only_by_granter() {
+    let mut deps = setup(300_000_000);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateOperators {
+            add: Some(vec![OperatorMsg {
+                address: "onramp".to_string(),
+                daily_limit: None,
+            }]),
+            remove: None,
+        },
+    )
+    .unwrap();
+
+    // Operators & owner can't redirect the granter's funds
+    for sender in ["onramp", "owner", "user1"] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &coins(1_800_000, "uusd")),
+            ExecuteMsg::DepositStableAuthorizedBatch {
+                granter: "employer".to_string(),
+                deposits: vec![AuthorizedDepositMsg {
+                    recipient: sender.to_string(),
+                    amount: Uint128::from(100_000_000_u64),
+                }],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err);
+    }
+}
@@ -0,0 +1,183 @@
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{from_binary, OwnedDeps, Uint128};
+use cw20_base::state::TOKEN_INFO;
+use std::str::FromStr;
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{DepositCapacityResponse, DepositCapsMsg, ExecuteMsg, QueryMsg};
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{deposit_stable, instantiate_contract, mock_dependencies};
+
+/// Instantiate with a total cap of 150 aliceUST and 80 aliceUST per address
+fn capped_dependencies() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            redeem_fee_ratio: None,
+            redeem_fee_tiers: None,
+            deposit_fee_ratio: None,
+            referral_share: None,
+            fee_recipients: None,
+            deposit_caps: Some(DepositCapsMsg {
+                total_cap: Some(Uint128::from(150_000_000_u64)),
+                address_cap: Some(Uint128::from(80_000_000_u64)),
+            }),
+            swap_denoms: None,
+            tax_policy: None,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn query_capacity(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    address: &str,
+) -> DepositCapacityResponse {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::DepositCapacity {
+            address: Some(address.to_string()),
+        },
+    )
+    .unwrap();
+    from_binary(&res).unwrap()
+}
+
+#[test]
+fn update_deposit_caps_unauthorized() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::UpdateConfig {
+            redeem_fee_ratio: None,
+            redeem_fee_tiers: None,
+            deposit_fee_ratio: None,
+            referral_share: None,
+            fee_recipients: None,
+            deposit_caps: Some(DepositCapsMsg {
+                total_cap: None,
+                address_cap: None,
+            }),
+            swap_denoms: None,
+            tax_policy: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    // Uncapped by default
+    assert_eq!(
+        query_capacity(&deps, "user1"),
+        DepositCapacityResponse {
+            total_cap: None,
+            total_remaining: None,
+            address_cap: None,
+            address_remaining: None,
+        }
+    );
+}
+
+#[test]
+fn deposit_within_caps() {
+    let mut deps = capped_dependencies();
+    deposit_stable(&mut deps, "user1", 60_000_000, None, 60_000_000).unwrap();
+
+    assert_eq!(
+        query_capacity(&deps, "user1"),
+        DepositCapacityResponse {
+            total_cap: Some(Uint128::from(150_000_000_u64)),
+            total_remaining: Some(Uint128::from(90_000_000_u64)),
+            address_cap: Some(Uint128::from(80_000_000_u64)),
+            address_remaining: Some(Uint128::from(20_000_000_u64)),
+        }
+    );
+}
+
+#[test]
+fn deposit_exceeds_address_cap() {
+    let mut deps = capped_dependencies();
+
+    let err = deposit_stable(&mut deps, "user1", 100_000_000, None, 100_000_000).unwrap_err();
+    assert_eq!(ContractError::AddressDepositCapReached {}, err);
+}
+
+#[test]
+fn deposit_exceeds_total_cap() {
+    let mut deps = capped_dependencies();
+    deposit_stable(&mut deps, "user1", 80_000_000, None, 80_000_000).unwrap();
+
+    // Recipient already at address cap, rejected before depositing into Anchor
+    let err = deposit_stable(&mut deps, "user1", 1_000_000, None, 81_000_000).unwrap_err();
+    assert_eq!(ContractError::AddressDepositCapReached {}, err);
+
+    // Expected mint past the total cap, rejected before depositing into Anchor
+    deposit_stable(&mut deps, "user2", 60_000_000, None, 140_000_000).unwrap();
+    let err = deposit_stable(&mut deps, "user3", 20_000_000, None, 160_000_000).unwrap_err();
+    assert_eq!(ContractError::TotalDepositCapReached {}, err);
+
+    // Actual mint past the total cap fails in the reply
+    let err = deposit_stable(&mut deps, "user3", 10_000_000, None, 151_000_000).unwrap_err();
+    assert_eq!(ContractError::TotalDepositCapReached {}, err);
+
+    // aUST of the failed reply is still held in this mock
+    deposit_stable(&mut deps, "user3", 10_000_000, None, 161_000_000).unwrap();
+    let err = deposit_stable(&mut deps, "user4", 1_000_000, None, 162_000_000).unwrap_err();
+    assert_eq!(ContractError::TotalDepositCapReached {}, err);
+}
+
+#[test]
+fn deposit_fee_not_counted_against_total_cap() {
+    let mut deps = capped_dependencies();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            redeem_fee_ratio: None,
+            redeem_fee_tiers: None,
+            deposit_fee_ratio: Some(Decimal256::percent(10)),
+            referral_share: None,
+            fee_recipients: None,
+            deposit_caps: None,
+            swap_denoms: None,
+            tax_policy: None,
+        },
+    )
+    .unwrap();
+
+    // Total cap is not the cw20 minter cap
+    assert_eq!(None, TOKEN_INFO.load(&deps.storage).unwrap().get_cap());
+
+    deposit_stable(&mut deps, "user1", 80_000_000, None, 80_000_000).unwrap();
+    deposit_stable(&mut deps, "user2", 70_000_000, None, 150_000_000).unwrap();
+    assert_eq!(
+        Uint128::from(150_000_000_u64),
+        TOKEN_INFO.load(&deps.storage).unwrap().total_supply
+    );
+}
+
+#[test]
+fn deposit_exceeds_address_cap_at_exchange_rate() {
+    let mut deps = capped_dependencies();
+    deps.querier
+        .with_exchange_rate(Decimal256::from_str("0.5").unwrap());
+
+    // 50 UST is expected to mint 100 aliceUST, over the 80 aliceUST address cap
+    let err = deposit_stable(&mut deps, "user1", 50_000_000, None, 100_000_000).unwrap_err();
+    assert_eq!(ContractError::AddressDepositCapReached {}, err);
+    deposit_stable(&mut deps, "user1", 40_000_000, None, 80_000_000).unwrap();
+}
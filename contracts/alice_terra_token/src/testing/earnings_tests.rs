@@ -0,0 +1,121 @@
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::testing::{
+    mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{coins, OwnedDeps, Uint128};
+use std::str::FromStr;
+
+use crate::contract::{execute, reply};
+use crate::msg::{EarningsResponse, ExecuteMsg};
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{
+    deposit, instantiate_contract, mock_dependencies, query_earnings, OK_REDEEM_REPLY,
+};
+
+/// Deposit 100,000,000 uusd for user1 -> 100,000,000 ualiceUST
+fn deposit_user1() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+
+    deps
+}
+
+#[test]
+fn earnings_after_deposit() {
+    let mut deps = deposit_user1();
+    assert_eq!(
+        EarningsResponse {
+            principal: Uint128::from(100_000_000_u64),
+            current_value: Uint128::from(100_000_000_u64),
+            lifetime_yield: Uint128::zero(),
+        },
+        query_earnings(&deps, "user1")
+    );
+
+    deps.querier
+        .with_exchange_rate(Decimal256::from_str("1.1").unwrap());
+    assert_eq!(
+        EarningsResponse {
+            principal: Uint128::from(100_000_000_u64),
+            current_value: Uint128::from(110_000_000_u64),
+            lifetime_yield: Uint128::from(10_000_000_u64),
+        },
+        query_earnings(&deps, "user1")
+    );
+}
+
+#[test]
+fn earnings_follow_transfers_and_redeems() {
+    let mut deps = deposit_user1();
+    deps.querier
+        .with_exchange_rate(Decimal256::from_str("1.1").unwrap());
+
+    // Transferred aliceUST moves principal at the current exchange rate
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::Transfer {
+            recipient: "user2".to_string(),
+            amount: Uint128::from(50_000_000_u64),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        EarningsResponse {
+            principal: Uint128::from(45_000_000_u64),
+            current_value: Uint128::from(55_000_000_u64),
+            lifetime_yield: Uint128::from(10_000_000_u64),
+        },
+        query_earnings(&deps, "user1")
+    );
+    assert_eq!(
+        EarningsResponse {
+            principal: Uint128::from(55_000_000_u64),
+            current_value: Uint128::from(55_000_000_u64),
+            lifetime_yield: Uint128::zero(),
+        },
+        query_earnings(&deps, "user2")
+    );
+
+    // Redeem the remaining 50,000,000 ualiceUST for 60,000,000 uusd
+    deps.querier
+        .with_exchange_rate(Decimal256::from_str("1.2").unwrap());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(50_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
+        },
+    )
+    .unwrap();
+    deps.querier.with_base(MockQuerier::new(&[(
+        MOCK_CONTRACT_ADDR,
+        &coins(60_000_000, "uusd"),
+    )]));
+    reply(deps.as_mut(), mock_env(), OK_REDEEM_REPLY).unwrap();
+
+    assert_eq!(
+        EarningsResponse {
+            principal: Uint128::zero(),
+            current_value: Uint128::zero(),
+            lifetime_yield: Uint128::from(15_000_000_u64),
+        },
+        query_earnings(&deps, "user1")
+    );
+    assert_eq!(
+        EarningsResponse {
+            principal: Uint128::from(55_000_000_u64),
+            current_value: Uint128::from(60_000_000_u64),
+            lifetime_yield: Uint128::from(5_000_000_u64),
+        },
+        query_earnings(&deps, "user2")
+    );
+}
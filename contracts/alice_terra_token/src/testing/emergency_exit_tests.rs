@@ -0,0 +1,346 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, BankMsg, ContractResult, CosmosMsg, OwnedDeps, Reply, SubMsg,
+    Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::anchor::MarketCw20HookMsg;
+use crate::contract::{execute, migrate, query, reply};
+use crate::error::ContractError;
+use crate::execute::EMERGENCY_EXIT_REPLY_ID;
+use crate::msg::{
+    EarningsResponse, EmergencyExitResponse, ExecuteMsg, MigrateMsg, QueryMsg, SolvencyResponse,
+};
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{
+    deposit, instantiate_contract, mock_dependencies, query_earnings, set_aterra_balance,
+    set_stable_balance, OK_SUBMSG_RESULT,
+};
+
+fn query_emergency_exit(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+) -> EmergencyExitResponse {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::EmergencyExit {}).unwrap();
+    from_binary(&res).unwrap()
+}
+
+/// user1 & user2 deposit 100 UST each for 60 & 40 aUST,
+/// the exit redeems the 100 aUST for 120 UST
+fn exited_dependencies() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    deposit(&mut deps, "user1", 60_000_000).unwrap();
+    deposit(&mut deps, "user2", 100_000_000).unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap();
+    set_aterra_balance(&mut deps, 0);
+    set_stable_balance(&mut deps, 120_000_000);
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: EMERGENCY_EXIT_REPLY_ID,
+            result: OK_SUBMSG_RESULT,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+#[test]
+fn emergency_exit() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    deposit(&mut deps, "user1", 60_000_000).unwrap();
+    deposit(&mut deps, "user2", 100_000_000).unwrap();
+    assert_eq!(None, query_emergency_exit(&deps).exit);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    // Redeem the entire aUST position
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "aterra_token_addr".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "money_market_addr".to_string(),
+                    amount: Uint128::from(100_000_000_u64),
+                    msg: to_binary(&MarketCw20HookMsg::RedeemStable {}).unwrap(),
+                })
+                .unwrap(),
+            }),
+            EMERGENCY_EXIT_REPLY_ID,
+        )]
+    );
+
+    set_aterra_balance(&mut deps, 0);
+    set_stable_balance(&mut deps, 120_000_000);
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: EMERGENCY_EXIT_REPLY_ID,
+            result: OK_SUBMSG_RESULT,
+        },
+    )
+    .unwrap();
+
+    let exit = query_emergency_exit(&deps).exit.unwrap();
+    assert_eq!(Uint128::from(100_000_000_u64), exit.aterra_amount);
+    assert_eq!(Uint128::from(120_000_000_u64), exit.stable_amount);
+
+    // Deposits are disabled
+    let err = deposit(&mut deps, "user1", 0).unwrap_err();
+    assert_eq!(ContractError::EmergencyExited {}, err);
+
+    // Redeem pays a pro-rata share of the UST held, without Anchor
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(60_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user1".to_string(),
+            amount: coins(72_000_000, "uusd"),
+        }))]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::EmergencyExited {}, err);
+}
+
+#[test]
+fn exited_without_anchor() {
+    let mut deps = exited_dependencies();
+    deps.querier.with_market_unavailable();
+
+    // aliceUST is valued at the 1.2 exit rate
+    assert_eq!(
+        Uint128::from(72_000_000_u64),
+        query_earnings(&deps, "user1").current_value
+    );
+
+    // Principal moves at the exit rate
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::Transfer {
+            recipient: "user2".to_string(),
+            amount: Uint128::from(10_000_000_u64),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        EarningsResponse {
+            principal: Uint128::from(112_000_000_u64),
+            current_value: Uint128::from(60_000_000_u64),
+            lifetime_yield: Uint128::zero(),
+        },
+        query_earnings(&deps, "user2")
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(50_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::RecordRate {},
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::EmergencyExited {}, err);
+}
+
+#[test]
+fn guardian_emergency_exit() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            symbol: None,
+            money_market_addr: None,
+            aterra_token_addr: None,
+            redeem_fee_ratio: None,
+            swap_denoms: None,
+            deposit_fee_ratio: None,
+            fee_recipients: None,
+            guardian: Some("guardian".to_string()),
+            tax_policy: None,
+            yield_backend: None,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap();
+    assert_eq!(1, res.messages.len());
+}
+
+#[test]
+fn failed_emergency_exit_retried() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap();
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: EMERGENCY_EXIT_REPLY_ID,
+            result: ContractResult::Err("Not enough uusd available".to_string()),
+        },
+    )
+    .unwrap_err();
+
+    // Not exited, deposits & a new exit are still possible
+    assert_eq!(None, query_emergency_exit(&deps).exit);
+    deposit(&mut deps, "user2", 200_000_000).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap();
+}
+
+#[test]
+fn exited_rejects_anchor_operations() {
+    let mut deps = exited_dependencies();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(10_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: Some("ukrw".to_string()),
+            min_target_amount: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::UnsupportedTargetDenom {}, err);
+
+    for msg in [
+        ExecuteMsg::ProcessWithdrawals { limit: None },
+        ExecuteMsg::ProcessSchedules { limit: None },
+    ] {
+        let err = execute(deps.as_mut(), mock_env(), mock_info("keeper", &[]), msg).unwrap_err();
+        assert_eq!(ContractError::EmergencyExited {}, err);
+    }
+}
+
+#[test]
+fn emergency_exit_without_position() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    set_aterra_balance(&mut deps, 0);
+
+    // Exits directly, without a zero amount redeem
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    let exit = query_emergency_exit(&deps).exit.unwrap();
+    assert_eq!(Uint128::zero(), exit.aterra_amount);
+    assert_eq!(Uint128::zero(), exit.stable_amount);
+}
+
+#[test]
+fn exited_solvency() {
+    let mut deps = exited_dependencies();
+    deps.querier.with_market_unavailable();
+
+    // 120 UST at the 1.2 exit rate back the 100 aliceUST
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Solvency {}).unwrap();
+    assert_eq!(
+        SolvencyResponse {
+            total_supply: Uint128::from(100_000_000_u64),
+            aterra_balance: Uint128::zero(),
+            stable_reserve: Uint128::from(120_000_000_u64),
+            surplus: Uint128::zero(),
+            deficit: Uint128::zero(),
+        },
+        from_binary(&res).unwrap()
+    );
+
+    set_stable_balance(&mut deps, 108_000_000);
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Solvency {}).unwrap();
+    let solvency: SolvencyResponse = from_binary(&res).unwrap();
+    assert_eq!(Uint128::from(10_000_000_u64), solvency.deficit);
+}
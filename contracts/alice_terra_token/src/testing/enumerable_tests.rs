@@ -0,0 +1,161 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{attr, from_binary, OwnedDeps, Uint128};
+use cw20::AllAccountsResponse;
+
+use crate::contract::{execute, migrate, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, HolderCountResponse, MigrateMsg, QueryMsg};
+use crate::state::holder_count_mut;
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{deposit, instantiate_contract, mock_dependencies};
+
+fn transfer(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    sender: &str,
+    recipient: &str,
+    amount: u64,
+) {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        },
+    )
+    .unwrap();
+}
+
+fn query_holder_count(deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) -> u64 {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::HolderCount {}).unwrap();
+    let value: HolderCountResponse = from_binary(&res).unwrap();
+    value.holder_count
+}
+
+fn query_all_accounts(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    start_after: Option<&str>,
+    limit: Option<u32>,
+) -> Vec<String> {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::AllAccounts {
+            start_after: start_after.map(|address| address.to_string()),
+            limit,
+        },
+    )
+    .unwrap();
+    let value: AllAccountsResponse = from_binary(&res).unwrap();
+    value.accounts
+}
+
+#[test]
+fn holder_count_follows_balances() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    assert_eq!(0, query_holder_count(&deps));
+
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+    deposit(&mut deps, "user2", 200_000_000).unwrap();
+    assert_eq!(2, query_holder_count(&deps));
+
+    // Transfers to self & between holders keep the count
+    transfer(&mut deps, "user1", "user1", 100_000_000);
+    transfer(&mut deps, "user1", "user2", 50_000_000);
+    assert_eq!(2, query_holder_count(&deps));
+
+    // user2 leaves, user3 joins
+    transfer(&mut deps, "user2", "user3", 150_000_000);
+    assert_eq!(2, query_holder_count(&deps));
+
+    transfer(&mut deps, "user1", "user3", 50_000_000);
+    assert_eq!(1, query_holder_count(&deps));
+}
+
+#[test]
+fn all_accounts() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+    deposit(&mut deps, "user2", 200_000_000).unwrap();
+    transfer(&mut deps, "user2", "user3", 100_000_000);
+
+    assert_eq!(
+        vec![
+            "user1".to_string(),
+            "user2".to_string(),
+            "user3".to_string()
+        ],
+        query_all_accounts(&deps, None, None)
+    );
+    assert_eq!(
+        vec!["user2".to_string()],
+        query_all_accounts(&deps, Some("user1"), Some(1))
+    );
+}
+
+fn recount_holders(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) -> bool {
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::RecountHolders { limit: Some(2) },
+    )
+    .unwrap();
+    res.attributes.contains(&attr("done", "true"))
+}
+
+#[test]
+fn migrate_recounts_holders() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+    deposit(&mut deps, "user2", 200_000_000).unwrap();
+    transfer(&mut deps, "user2", "user1", 100_000_000);
+    deposit(&mut deps, "user3", 300_000_000).unwrap();
+    deposit(&mut deps, "user4", 400_000_000).unwrap();
+
+    // Legacy contract without holder count
+    holder_count_mut(&mut deps.storage).remove();
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            symbol: None,
+            money_market_addr: None,
+            aterra_token_addr: None,
+            redeem_fee_ratio: None,
+            swap_denoms: None,
+            deposit_fee_ratio: None,
+            fee_recipients: None,
+            guardian: None,
+            tax_policy: None,
+            yield_backend: None,
+        },
+    )
+    .unwrap();
+    query(deps.as_ref(), mock_env(), QueryMsg::HolderCount {}).unwrap_err();
+
+    // user1 & user2 counted
+    assert!(!recount_holders(&mut deps));
+
+    // user1 was counted & leaves, user5 joins & is counted when reached
+    transfer(&mut deps, "user1", "user5", 200_000_000);
+
+    assert!(!recount_holders(&mut deps));
+    assert!(recount_holders(&mut deps));
+    assert_eq!(3, query_holder_count(&deps));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::RecountHolders { limit: None },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::NoHolderRecount {}, err);
+}
@@ -0,0 +1,386 @@
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{attr, coins, from_binary, DepsMut, Event, OwnedDeps, StdError, Uint128};
+use std::str::FromStr;
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::msg::{
+    DepositCapsMsg, ExecuteMsg, FeeExemptionsResponse, FeeRecipientMsg, FeeScheduleResponse,
+    InstantiateMsg, QueryMsg,
+};
+use crate::state::RedeemFeeTier;
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{
+    deposit, instantiate_contract, mock_dependencies, query_balance, OK_DEPOSIT_REPLY,
+};
+
+fn fee_instantiate_msg(fee_recipients: Vec<(&str, &str)>) -> InstantiateMsg {
+    InstantiateMsg {
+        owner: "owner".to_string(),
+        name: String::from("Alice Terra USD"),
+        symbol: String::from("aliceUST"),
+        decimals: 6,
+        stable_denom: String::from("uusd"),
+        money_market_addr: String::from("money_market_addr"),
+        aterra_token_addr: String::from("aterra_token_addr"),
+        redeem_fee_ratio: Decimal256::from_str("0.01").unwrap(),
+        swap_denoms: None,
+        total_deposit_cap: None,
+        address_deposit_cap: None,
+        deposit_fee_ratio: Some(Decimal256::from_str("0.001").unwrap()),
+        fee_recipients: Some(
+            fee_recipients
+                .into_iter()
+                .map(|(address, share)| FeeRecipientMsg {
+                    address: address.to_string(),
+                    share: Decimal256::from_str(share).unwrap(),
+                })
+                .collect(),
+        ),
+        redeem_fee_tiers: None,
+        referral_share: None,
+        guardian: None,
+        tax_policy: None,
+        yield_backend: None,
+    }
+}
+
+/// 0.1% deposit fee and 1% redeem fee split 70/30 between treasury and dev
+fn instantiate_fee_contract(deps: DepsMut) {
+    let msg = fee_instantiate_msg(vec![("treasury", "0.7"), ("dev", "0.3")]);
+    instantiate(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+}
+
+/// Redeem burn_amount aliceUST from sender to recipient, returns the redeem fee attribute
+fn redeem_fee(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    sender: &str,
+    recipient: &str,
+    burn_amount: u64,
+) -> String {
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: Some(recipient.to_string()),
+            burn_amount: Uint128::from(burn_amount),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
+        },
+    )
+    .unwrap();
+    res.attributes
+        .into_iter()
+        .find(|attribute| attribute.key == "redeem_fee_amount")
+        .unwrap()
+        .value
+}
+
+#[test]
+fn instantiate_invalid_fee_recipients() {
+    let mut deps = mock_dependencies(&[]);
+
+    // Shares must sum to 1
+    let msg = fee_instantiate_msg(vec![("treasury", "0.7"), ("dev", "0.2")]);
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+
+    let mut msg = fee_instantiate_msg(vec![]);
+    msg.deposit_fee_ratio = Some(Decimal256::from_str("1.1").unwrap());
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+}
+
+#[test]
+fn deposit_fee_minted_to_recipients() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_fee_contract(deps.as_mut());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &coins(100_000_000, "uusd")),
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+    deps.querier.with_token_balances(&[(
+        &"aterra_token_addr".to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(100_000_000_u64),
+        )],
+    )]);
+    let res = reply(deps.as_mut(), mock_env(), OK_DEPOSIT_REPLY).unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("deposit_fee_amount", "100000")));
+
+    assert_eq!(Uint128::from(99_900_000_u64), query_balance(&deps, "user1"));
+    assert_eq!(Uint128::from(70_000_u64), query_balance(&deps, "treasury"));
+    assert_eq!(Uint128::from(30_000_u64), query_balance(&deps, "dev"));
+
+    // Owner deposits are fee exempt
+    deposit(&mut deps, "owner", 200_000_000).unwrap();
+    assert_eq!(
+        Uint128::from(100_000_000_u64),
+        query_balance(&deps, "owner")
+    );
+}
+
+#[test]
+fn redeem_fee_split_to_recipients() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_fee_contract(deps.as_mut());
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(10_000_100_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
+        },
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&attr("redeem_fee_amount", "100001")));
+
+    // Last recipient receives the rounding remainder
+    assert_eq!(Uint128::from(140_000_u64), query_balance(&deps, "treasury"));
+    assert_eq!(Uint128::from(60_001_u64), query_balance(&deps, "dev"));
+}
+
+#[test]
+fn fees_default_to_owner() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = fee_instantiate_msg(vec![]);
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+
+    assert_eq!(Uint128::from(99_900_000_u64), query_balance(&deps, "user1"));
+    assert_eq!(Uint128::from(100_000_u64), query_balance(&deps, "owner"));
+}
+
+#[test]
+fn tiered_redeem_fees() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_fee_contract(deps.as_mut());
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+
+    let tiers = vec![
+        RedeemFeeTier {
+            min_amount: Uint128::from(10_000_000_u64),
+            fee_ratio: Decimal256::from_str("0.005").unwrap(),
+        },
+        RedeemFeeTier {
+            min_amount: Uint128::from(50_000_000_u64),
+            fee_ratio: Decimal256::zero(),
+        },
+    ];
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::UpdateConfig {
+            redeem_fee_ratio: None,
+            redeem_fee_tiers: Some(tiers.clone()),
+            deposit_fee_ratio: None,
+            referral_share: None,
+            fee_recipients: None,
+            deposit_caps: None,
+            swap_denoms: None,
+            tax_policy: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    // Tiers must be sorted
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            redeem_fee_ratio: None,
+            redeem_fee_tiers: Some(tiers.iter().rev().cloned().collect()),
+            deposit_fee_ratio: None,
+            referral_share: None,
+            fee_recipients: None,
+            deposit_caps: None,
+            swap_denoms: None,
+            tax_policy: None,
+        },
+    )
+    .unwrap_err();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            redeem_fee_ratio: None,
+            redeem_fee_tiers: Some(tiers.clone()),
+            deposit_fee_ratio: Some(Decimal256::zero()),
+            referral_share: None,
+            fee_recipients: None,
+            deposit_caps: None,
+            swap_denoms: None,
+            tax_policy: None,
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::FeeSchedule {}).unwrap();
+    let fee_schedule: FeeScheduleResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        fee_schedule,
+        FeeScheduleResponse {
+            deposit_fee_ratio: Decimal256::zero(),
+            redeem_fee_ratio: Decimal256::from_str("0.01").unwrap(),
+            redeem_fee_tiers: tiers,
+            referral_share: Decimal256::zero(),
+        }
+    );
+
+    assert_eq!("10000", redeem_fee(&mut deps, "user1", "user1", 1_000_000));
+    assert_eq!("50000", redeem_fee(&mut deps, "user1", "user1", 10_000_000));
+    assert_eq!("0", redeem_fee(&mut deps, "user1", "user1", 50_000_000));
+}
+
+#[test]
+fn fee_exemptions() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_fee_contract(deps.as_mut());
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::UpdateFeeExemptions {
+            add: Some(vec!["user1".to_string()]),
+            remove: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateFeeExemptions {
+            add: Some(vec!["merchant".to_string(), "partner".to_string()]),
+            remove: None,
+        },
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::FeeExemptions {
+            start_after: Some("merchant".to_string()),
+            limit: None,
+        },
+    )
+    .unwrap();
+    let exemptions: FeeExemptionsResponse = from_binary(&res).unwrap();
+    assert_eq!(vec!["partner".to_string()], exemptions.addresses);
+
+    // Redeems to exempt recipients pay no fee
+    assert_eq!("0", redeem_fee(&mut deps, "user1", "merchant", 1_000_000));
+    assert_eq!("10000", redeem_fee(&mut deps, "user1", "user2", 1_000_000));
+
+    // Deposits to exempt recipients pay no fee
+    deposit(&mut deps, "partner", 200_000_000).unwrap();
+    assert_eq!(
+        Uint128::from(100_000_000_u64),
+        query_balance(&deps, "partner")
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateFeeExemptions {
+            add: None,
+            remove: Some(vec!["merchant".to_string()]),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        "10000",
+        redeem_fee(&mut deps, "user1", "merchant", 1_000_000)
+    );
+}
+
+#[test]
+fn update_config_fee_event() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    let update_fee_recipients = |deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+                                 shares: &[(&str, &str)]| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            ExecuteMsg::UpdateConfig {
+                redeem_fee_ratio: Some(Decimal256::from_str("0.01").unwrap()),
+                redeem_fee_tiers: None,
+                deposit_fee_ratio: None,
+                referral_share: Some(Decimal256::from_str("0.2").unwrap()),
+                fee_recipients: Some(
+                    shares
+                        .iter()
+                        .map(|(address, share)| FeeRecipientMsg {
+                            address: address.to_string(),
+                            share: Decimal256::from_str(share).unwrap(),
+                        })
+                        .collect(),
+                ),
+                deposit_caps: Some(DepositCapsMsg {
+                    total_cap: Some(Uint128::from(150_000_000_u64)),
+                    address_cap: None,
+                }),
+                swap_denoms: None,
+                tax_policy: None,
+            },
+        )
+    };
+
+    // Validated by save_config
+    let err = update_fee_recipients(&mut deps, &[("treasury", "0.5")]).unwrap_err();
+    assert_eq!(
+        ContractError::Std(StdError::generic_err("fee_recipients shares must sum to 1")),
+        err
+    );
+
+    let res = update_fee_recipients(&mut deps, &[("treasury", "0.7"), ("partner", "0.3")]).unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new("update_config")
+            .add_attribute("old_redeem_fee_ratio", "0")
+            .add_attribute("new_redeem_fee_ratio", "0.01")
+            .add_attribute("old_referral_share", "0")
+            .add_attribute("new_referral_share", "0.2")
+            .add_attribute("old_fee_recipients", "")
+            .add_attribute("new_fee_recipients", "treasury:0.7,partner:0.3")
+            .add_attribute("old_total_deposit_cap", "none")
+            .add_attribute("new_total_deposit_cap", "150000000")
+            .add_attribute("old_address_deposit_cap", "none")
+            .add_attribute("new_address_deposit_cap", "none")]
+    );
+}
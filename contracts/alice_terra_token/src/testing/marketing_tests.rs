@@ -0,0 +1,127 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{from_binary, Addr, Binary, OwnedDeps};
+use cw20::{DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo, MarketingInfoResponse};
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{instantiate_contract, mock_dependencies};
+
+const PNG_HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+fn query_marketing_info(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+) -> MarketingInfoResponse {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::MarketingInfo {}).unwrap();
+    from_binary(&res).unwrap()
+}
+
+#[test]
+fn update_marketing() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    let msg = ExecuteMsg::UpdateMarketing {
+        project: Some("https://alice.money".to_string()),
+        description: Some("Interest bearing UST".to_string()),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        MarketingInfoResponse {
+            project: Some("https://alice.money".to_string()),
+            description: Some("Interest bearing UST".to_string()),
+            marketing: Some(Addr::unchecked("owner")),
+            logo: None,
+        },
+        query_marketing_info(&deps)
+    );
+
+    // Empty strings unset fields
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateMarketing {
+            project: Some("".to_string()),
+            description: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        MarketingInfoResponse {
+            project: None,
+            description: Some("Interest bearing UST".to_string()),
+            marketing: Some(Addr::unchecked("owner")),
+            logo: None,
+        },
+        query_marketing_info(&deps)
+    );
+}
+
+#[test]
+fn upload_logo() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    let url_logo = Logo::Url("https://alice.money/logo.svg".to_string());
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::UploadLogo(url_logo.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UploadLogo(url_logo),
+    )
+    .unwrap();
+    assert_eq!(
+        Some(LogoInfo::Url("https://alice.money/logo.svg".to_string())),
+        query_marketing_info(&deps).logo
+    );
+    // URL logos are not downloadable
+    query(deps.as_ref(), mock_env(), QueryMsg::DownloadLogo {}).unwrap_err();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UploadLogo(Logo::Embedded(EmbeddedLogo::Png(Binary::from(b"GIF89a")))),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::InvalidPngHeader {}, err);
+
+    let png = Binary::from(PNG_HEADER.to_vec());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UploadLogo(Logo::Embedded(EmbeddedLogo::Png(png.clone()))),
+    )
+    .unwrap();
+    assert_eq!(Some(LogoInfo::Embedded), query_marketing_info(&deps).logo);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::DownloadLogo {}).unwrap();
+    assert_eq!(
+        DownloadLogoResponse {
+            mime_type: "image/png".to_string(),
+            data: png,
+        },
+        from_binary(&res).unwrap()
+    );
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use cosmwasm_bignumber::{Decimal256, Uint256};
 use cosmwasm_std::testing::MockQuerier;
 use cosmwasm_std::{
     from_binary, from_slice, to_binary, ContractResult, Decimal, Querier, QuerierResult,
@@ -8,6 +9,18 @@ use cosmwasm_std::{
 use cw20::{BalanceResponse, Cw20QueryMsg};
 use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
 
+use crate::anchor::{EpochStateResponse, MarketQueryMsg};
+use crate::authz::{
+    GENERIC_AUTHORIZATION_TYPE_URL, GRANTS_QUERY_PATH, MSG_SEND_TYPE_URL,
+    SEND_AUTHORIZATION_TYPE_URL,
+};
+use crate::utils::proto_encode;
+use cosmos_sdk_proto::cosmos::authz::v1beta1::{
+    GenericAuthorization, Grant, QueryGrantsRequest, QueryGrantsResponse,
+};
+use cosmos_sdk_proto::cosmos::bank::v1beta1::SendAuthorization;
+use prost::Message;
+
 // Mock querier for Anchor money market contract and aTerra token contract queries
 // Reference: https://github.com/Anchor-Protocol/money-market-contracts/blob/61918b1f348d1ee2cc8271ce79ff9d4486bd0174/contracts/overseer/src/testing/mock_querier.rs
 
@@ -15,6 +28,63 @@ pub struct WasmMockQuerier {
     base: MockQuerier<TerraQueryWrapper>,
     tax_querier: TaxQuerier,
     token_querier: TokenQuerier,
+    authz_querier: AuthzQuerier,
+    // None once the money market is unavailable
+    exchange_rate: Option<Decimal256>,
+}
+
+#[derive(Clone, Default)]
+pub struct AuthzQuerier {
+    // SendAuthorization spend limits to any grantee
+    // {granter: [(denom, amount)]}
+    spend_limits: HashMap<String, Vec<(String, Uint128)>>,
+    // Granters of a GenericAuthorization for MsgSend to any grantee
+    generic_granters: Vec<String>,
+}
+
+impl AuthzQuerier {
+    fn query_grants(&self, data: &[u8]) -> QuerierResult {
+        let request = QueryGrantsRequest::decode(data).unwrap();
+        let mut grants = match self.spend_limits.get(&request.granter) {
+            Some(spend_limit) => vec![Grant {
+                authorization: Some(prost_types::Any {
+                    type_url: SEND_AUTHORIZATION_TYPE_URL.to_string(),
+                    value: proto_encode(SendAuthorization {
+                        spend_limit: spend_limit
+                            .iter()
+                            .map(
+                                |(denom, amount)| cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+                                    denom: denom.clone(),
+                                    amount: amount.to_string(),
+                                },
+                            )
+                            .collect(),
+                    })
+                    .unwrap(),
+                }),
+                expiration: None,
+            }],
+            None => vec![],
+        };
+        if self.generic_granters.contains(&request.granter) {
+            grants.push(Grant {
+                authorization: Some(prost_types::Any {
+                    type_url: GENERIC_AUTHORIZATION_TYPE_URL.to_string(),
+                    value: proto_encode(GenericAuthorization {
+                        msg: MSG_SEND_TYPE_URL.to_string(),
+                    })
+                    .unwrap(),
+                }),
+                expiration: None,
+            });
+        }
+        let res = proto_encode(QueryGrantsResponse {
+            grants,
+            pagination: None,
+        })
+        .unwrap();
+        SystemResult::Ok(ContractResult::Ok(res.into()))
+    }
 }
 
 #[derive(Clone, Default)]
@@ -119,6 +189,23 @@ impl WasmMockQuerier {
                     panic!("DO NOT ENTER HERE")
                 }
             }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. })
+                if matches!(from_binary(msg), Ok(MarketQueryMsg::EpochState { .. })) =>
+            {
+                match self.exchange_rate {
+                    Some(exchange_rate) => {
+                        let res = EpochStateResponse {
+                            exchange_rate,
+                            aterra_supply: Uint256::zero(),
+                        };
+                        SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                    }
+                    None => SystemResult::Err(SystemError::InvalidRequest {
+                        error: "Money market unavailable".to_string(),
+                        request: msg.clone(),
+                    }),
+                }
+            }
             QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => match from_binary(msg) {
                 Ok(Cw20QueryMsg::Balance { address }) => {
                     match self.token_querier.balances.get(contract_addr) {
@@ -142,6 +229,9 @@ impl WasmMockQuerier {
                 }
                 _ => panic!("Unsupported Wasm query"),
             },
+            QueryRequest::Stargate { path, data } if path == GRANTS_QUERY_PATH => {
+                self.authz_querier.query_grants(data.as_slice())
+            }
             _ => self.base.handle_query(request),
         }
     }
@@ -153,6 +243,8 @@ impl WasmMockQuerier {
             base,
             tax_querier: TaxQuerier::default(),
             token_querier: TokenQuerier::default(),
+            authz_querier: AuthzQuerier::default(),
+            exchange_rate: Some(Decimal256::one()),
         }
     }
 
@@ -169,4 +261,38 @@ impl WasmMockQuerier {
     pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
         self.token_querier = TokenQuerier::new(balances);
     }
+
+    /// Sets the SendAuthorization spend limits granted by each granter
+    #[allow(dead_code)]
+    pub fn with_send_authorizations(&mut self, spend_limits: &[(&str, &[(&str, u128)])]) {
+        self.authz_querier.spend_limits = spend_limits
+            .iter()
+            .map(|(granter, spend_limit)| {
+                (
+                    granter.to_string(),
+                    spend_limit
+                        .iter()
+                        .map(|(denom, amount)| (denom.to_string(), Uint128::from(*amount)))
+                        .collect(),
+                )
+            })
+            .collect();
+    }
+
+    /// Sets the granters of a GenericAuthorization for MsgSend
+    #[allow(dead_code)]
+    pub fn with_generic_send_authorizations(&mut self, granters: &[&str]) {
+        self.authz_querier.generic_granters =
+            granters.iter().map(|granter| granter.to_string()).collect();
+    }
+
+    #[allow(dead_code)]
+    pub fn with_exchange_rate(&mut self, exchange_rate: Decimal256) {
+        self.exchange_rate = Some(exchange_rate);
+    }
+
+    // Fail money market queries
+    pub fn with_market_unavailable(&mut self) {
+        self.exchange_rate = None;
+    }
 }
@@ -0,0 +1,94 @@
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::{
+    coins, to_binary, Addr, BankMsg, CosmosMsg, Deps, Response, StdResult, Storage, SubMsg,
+    Uint128, WasmMsg,
+};
+use cosmwasm_storage::{singleton, singleton_read};
+
+use crate::state::Config;
+use crate::yield_strategy::{set_test_strategy, YieldStrategy};
+
+pub const MOCK_MARKET_ADDR: &str = "mock_market_addr";
+pub const MOCK_ILLIQUID_ERROR: &str = "mock market illiquid";
+
+static MOCK_POSITION_KEY: &[u8] = b"mock_position";
+static MOCK_EXCHANGE_RATE_KEY: &[u8] = b"mock_exchange_rate";
+
+/// Yield backend of tests. Deposits are sent to MOCK_MARKET_ADDR, the position &
+/// exchange rate are set by tests in the contract storage
+pub struct MockStrategy {
+    stable_denom: String,
+}
+
+impl MockStrategy {
+    pub fn new(config: &Config) -> Self {
+        MockStrategy {
+            stable_denom: config.stable_denom.clone(),
+        }
+    }
+}
+
+/// Replaces the config yield backend with MockStrategy for the rest of the current test
+pub fn use_mock_strategy() {
+    set_test_strategy(|config| Box::new(MockStrategy::new(config)));
+}
+
+pub fn set_mock_position(storage: &mut dyn Storage, position: u128) {
+    singleton(storage, MOCK_POSITION_KEY)
+        .save(&Uint128::from(position))
+        .unwrap();
+}
+
+pub fn set_mock_exchange_rate(storage: &mut dyn Storage, exchange_rate: Decimal256) {
+    singleton(storage, MOCK_EXCHANGE_RATE_KEY)
+        .save(&exchange_rate)
+        .unwrap();
+}
+
+/// Submessage of MockStrategy::redeem_stable
+pub fn mock_redeem_msg(position_amount: u128) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: MOCK_MARKET_ADDR.to_string(),
+        funds: vec![],
+        msg: to_binary(&Uint128::from(position_amount)).unwrap(),
+    })
+}
+
+impl YieldStrategy for MockStrategy {
+    fn deposit_stable(&self, stable_amount: Uint128, reply_id: u64) -> StdResult<Response> {
+        Ok(Response::new()
+            .add_submessage(SubMsg::reply_always(
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: MOCK_MARKET_ADDR.to_string(),
+                    amount: coins(stable_amount.u128(), self.stable_denom.clone()),
+                }),
+                reply_id,
+            ))
+            .add_attribute("mock_deposit_amount", stable_amount))
+    }
+
+    fn redeem_stable(&self, position_amount: Uint128, reply_id: u64) -> StdResult<Response> {
+        Ok(Response::new()
+            .add_submessage(SubMsg::reply_always(
+                mock_redeem_msg(position_amount.u128()),
+                reply_id,
+            ))
+            .add_attribute("mock_redeem_amount", position_amount))
+    }
+
+    fn query_position(&self, deps: Deps, _account: Addr) -> StdResult<Uint128> {
+        Ok(singleton_read(deps.storage, MOCK_POSITION_KEY)
+            .may_load()?
+            .unwrap_or_default())
+    }
+
+    fn is_illiquid_error(&self, error: &str) -> bool {
+        error == MOCK_ILLIQUID_ERROR
+    }
+
+    fn query_exchange_rate(&self, deps: Deps, _block_height: u64) -> StdResult<Decimal256> {
+        Ok(singleton_read(deps.storage, MOCK_EXCHANGE_RATE_KEY)
+            .may_load()?
+            .unwrap_or_else(Decimal256::one))
+    }
+}
@@ -1,4 +1,26 @@
+mod deposit_batch_tests;
+mod deposit_caps_tests;
+mod earnings_tests;
+mod emergency_exit_tests;
+mod enumerable_tests;
+mod fees_tests;
+mod marketing_tests;
 mod mock_bech32_api;
 mod mock_querier;
+pub mod mock_strategy;
+mod operators_tests;
+mod ownership_tests;
+mod pause_tests;
+mod rate_history_tests;
+mod referral_tests;
 mod relay_tests;
+mod rescue_tests;
+mod schedules_tests;
+#[cfg(feature = "snapshot")]
+mod snapshot_tests;
+mod solvency_tests;
+mod swap_tests;
+mod tax_tests;
 mod tests;
+mod withdrawal_queue_tests;
+mod yield_strategy_tests;
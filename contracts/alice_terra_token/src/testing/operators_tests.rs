@@ -0,0 +1,235 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{coins, from_binary, Env, OwnedDeps, Response, Uint128};
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, OperatorInfo, OperatorMsg, OperatorsResponse, QueryMsg};
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{env_at, instantiate_contract, mock_dependencies};
+
+const DAY: u64 = 24 * 60 * 60;
+
+fn deposit_authorized(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    sender: &str,
+    amount: u64,
+) -> Result<Response, ContractError> {
+    deposit_authorized_at(deps, sender, amount, mock_env())
+}
+
+fn deposit_authorized_at(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    sender: &str,
+    amount: u64,
+    env: Env,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(sender, &coins(600_000, "uusd")),
+        ExecuteMsg::DepositStableAuthorized {
+            sender: None,
+            recipient: "user1".to_string(),
+            amount: Uint128::from(amount),
+            referrer: None,
+        },
+    )
+}
+
+/// Instantiate with a 1,000 UST grant from user1 & operator with daily_limit
+fn operator_dependencies(
+    operator: &str,
+    daily_limit: Option<u64>,
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    deps.querier
+        .with_send_authorizations(&[("user1", &[("uusd", 1_000_000_000)])]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateOperators {
+            add: Some(vec![OperatorMsg {
+                address: operator.to_string(),
+                daily_limit: daily_limit.map(Uint128::from),
+            }]),
+            remove: None,
+        },
+    )
+    .unwrap();
+    deps
+}
+
+fn query_operators(deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) -> Vec<OperatorInfo> {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Operators {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let res: OperatorsResponse = from_binary(&res).unwrap();
+    res.operators
+}
+
+#[test]
+fn operator_deposits() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    deps.querier
+        .with_send_authorizations(&[("user1", &[("uusd", 1_000_000_000)])]);
+
+    let err = deposit_authorized(&mut deps, "onramp", 100_000_000).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    let msg = ExecuteMsg::UpdateOperators {
+        add: Some(vec![
+            OperatorMsg {
+                address: "onramp".to_string(),
+                daily_limit: Some(Uint128::from(150_000_000_u64)),
+            },
+            OperatorMsg {
+                address: "partner".to_string(),
+                daily_limit: None,
+            },
+        ]),
+        remove: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("onramp", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    deposit_authorized(&mut deps, "onramp", 100_000_000).unwrap();
+    let err = deposit_authorized(&mut deps, "onramp", 100_000_000).unwrap_err();
+    assert_eq!(ContractError::OperatorDailyLimitReached {}, err);
+    deposit_authorized(&mut deps, "partner", 100_000_000).unwrap();
+
+    assert_eq!(
+        vec![
+            OperatorInfo {
+                address: "onramp".to_string(),
+                daily_limit: Some(Uint128::from(150_000_000_u64)),
+                used_today: Uint128::from(100_000_000_u64),
+            },
+            OperatorInfo {
+                address: "partner".to_string(),
+                daily_limit: None,
+                used_today: Uint128::from(100_000_000_u64),
+            },
+        ],
+        query_operators(&deps)
+    );
+
+    // Usage resets the next day
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(24 * 60 * 60);
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("onramp", &coins(600_000, "uusd")),
+        ExecuteMsg::DepositStableAuthorized {
+            sender: None,
+            recipient: "user1".to_string(),
+            amount: Uint128::from(100_000_000_u64),
+            referrer: None,
+        },
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateOperators {
+            add: None,
+            remove: Some(vec!["onramp".to_string()]),
+        },
+    )
+    .unwrap();
+    let err = deposit_authorized(&mut deps, "onramp", 1_000_000).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+    assert_eq!(1, query_operators(&deps).len());
+}
+
+#[test]
+fn operator_limit_resets_at_day_boundary() {
+    let mut deps = operator_dependencies("onramp", Some(150_000_000));
+    // Seconds from mock_env to the next day boundary
+    let next_day = DAY - mock_env().block.time.seconds() % DAY;
+
+    deposit_authorized_at(&mut deps, "onramp", 100_000_000, env_at(next_day - 1)).unwrap();
+    let err =
+        deposit_authorized_at(&mut deps, "onramp", 100_000_000, env_at(next_day - 1)).unwrap_err();
+    assert_eq!(ContractError::OperatorDailyLimitReached {}, err);
+
+    // Usage of the previous day doesn't count
+    deposit_authorized_at(&mut deps, "onramp", 150_000_000, env_at(next_day)).unwrap();
+    let err =
+        deposit_authorized_at(&mut deps, "onramp", 1, env_at(next_day + DAY - 1)).unwrap_err();
+    assert_eq!(ContractError::OperatorDailyLimitReached {}, err);
+}
+
+#[test]
+fn removed_operator_unauthorized() {
+    let mut deps = operator_dependencies("onramp", Some(150_000_000));
+    deposit_authorized(&mut deps, "onramp", 100_000_000).unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateOperators {
+            add: None,
+            remove: Some(vec!["onramp".to_string()]),
+        },
+    )
+    .unwrap();
+    let err = deposit_authorized(&mut deps, "onramp", 1_000_000).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+    assert_eq!(Vec::<OperatorInfo>::new(), query_operators(&deps));
+}
+
+#[test]
+fn operator_without_limit_unlimited() {
+    let mut deps = operator_dependencies("partner", None);
+
+    deposit_authorized(&mut deps, "partner", 500_000_000).unwrap();
+    deposit_authorized(&mut deps, "partner", 500_000_000).unwrap();
+    assert_eq!(
+        vec![OperatorInfo {
+            address: "partner".to_string(),
+            daily_limit: None,
+            used_today: Uint128::from(1_000_000_000_u64),
+        }],
+        query_operators(&deps)
+    );
+}
+
+#[test]
+fn owner_bypasses_operator_limits() {
+    let mut deps = operator_dependencies("onramp", Some(0));
+
+    let err = deposit_authorized(&mut deps, "onramp", 1).unwrap_err();
+    assert_eq!(ContractError::OperatorDailyLimitReached {}, err);
+
+    // Owner is not an operator & has no usage
+    deposit_authorized(&mut deps, "owner", 500_000_000).unwrap();
+    deposit_authorized(&mut deps, "owner", 500_000_000).unwrap();
+    assert_eq!(
+        vec![OperatorInfo {
+            address: "onramp".to_string(),
+            daily_limit: Some(Uint128::zero()),
+            used_today: Uint128::zero(),
+        }],
+        query_operators(&deps)
+    );
+}
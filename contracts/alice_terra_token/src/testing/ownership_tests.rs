@@ -0,0 +1,139 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{from_binary, Addr, OwnedDeps};
+use cw0::Expiration;
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, PendingOwnerResponse, QueryMsg};
+use crate::state::{Config, PendingOwner};
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{instantiate_contract, mock_dependencies};
+
+fn query_pending_owner(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+) -> Option<PendingOwner> {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+    let res: PendingOwnerResponse = from_binary(&res).unwrap();
+    res.pending_owner
+}
+
+fn query_owner(deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) -> Addr {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: Config = from_binary(&res).unwrap();
+    config.owner
+}
+
+fn propose_new_owner(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    sender: &str,
+    expires: Option<Expiration>,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::ProposeNewOwner {
+            owner: "new_owner".to_string(),
+            expires,
+        },
+    )?;
+    Ok(())
+}
+
+fn accept_ownership(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    sender: &str,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::AcceptOwnership {},
+    )?;
+    Ok(())
+}
+
+#[test]
+fn transfer_ownership() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    assert_eq!(None, query_pending_owner(&deps));
+
+    let err = propose_new_owner(&mut deps, "user1", None).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+    let err = accept_ownership(&mut deps, "new_owner").unwrap_err();
+    assert_eq!(ContractError::NoPendingOwner {}, err);
+
+    propose_new_owner(&mut deps, "owner", None).unwrap();
+    assert_eq!(
+        Some(PendingOwner {
+            owner: Addr::unchecked("new_owner"),
+            expires: Expiration::Never {},
+        }),
+        query_pending_owner(&deps)
+    );
+
+    let err = accept_ownership(&mut deps, "user1").unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    accept_ownership(&mut deps, "new_owner").unwrap();
+    assert_eq!(Addr::unchecked("new_owner"), query_owner(&deps));
+    assert_eq!(None, query_pending_owner(&deps));
+}
+
+#[test]
+fn ownership_proposal_expires() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    let env = mock_env();
+    let err = propose_new_owner(
+        &mut deps,
+        "owner",
+        Some(Expiration::AtHeight(env.block.height)),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Expired {}, err);
+
+    propose_new_owner(
+        &mut deps,
+        "owner",
+        Some(Expiration::AtHeight(env.block.height + 1)),
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.height += 1;
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info("new_owner", &[]),
+        ExecuteMsg::AcceptOwnership {},
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Expired {}, err);
+    assert_eq!(Addr::unchecked("owner"), query_owner(&deps));
+}
+
+#[test]
+fn cancel_ownership_proposal() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    propose_new_owner(&mut deps, "owner", None).unwrap();
+
+    let msg = ExecuteMsg::CancelOwnershipProposal {};
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("new_owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(None, query_pending_owner(&deps));
+
+    let err = accept_ownership(&mut deps, "new_owner").unwrap_err();
+    assert_eq!(ContractError::NoPendingOwner {}, err);
+}
@@ -0,0 +1,171 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{coins, from_binary, OwnedDeps, Uint128};
+
+use crate::contract::{execute, migrate, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, MigrateMsg, QueryMsg};
+use crate::state::PauseState;
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{instantiate_contract, mock_dependencies};
+
+fn set_guardian(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) {
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            symbol: None,
+            money_market_addr: None,
+            aterra_token_addr: None,
+            redeem_fee_ratio: None,
+            swap_denoms: None,
+            deposit_fee_ratio: None,
+            fee_recipients: None,
+            guardian: Some("guardian".to_string()),
+            tax_policy: None,
+            yield_backend: None,
+        },
+    )
+    .unwrap();
+}
+
+fn query_pause_state(deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) -> PauseState {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PauseState {}).unwrap();
+    from_binary(&res).unwrap()
+}
+
+fn deposit_msg() -> ExecuteMsg {
+    ExecuteMsg::DepositStable {
+        recipient: None,
+        min_stable_amount: None,
+        referrer: None,
+    }
+}
+
+#[test]
+fn pause_deposits() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    assert_eq!(PauseState::default(), query_pause_state(&deps));
+
+    let pause_msg = ExecuteMsg::Pause {
+        deposits: true,
+        redeems: false,
+        transfers: false,
+        relay: false,
+    };
+
+    // No guardian set, nobody can pause
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        pause_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    set_guardian(&mut deps);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        pause_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        pause_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        PauseState {
+            deposits: true,
+            ..PauseState::default()
+        },
+        query_pause_state(&deps)
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &coins(100_000_000, "uusd")),
+        deposit_msg(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Paused {}, err);
+
+    // Other switches are unaffected
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::Transfer {
+            recipient: "user2".to_string(),
+            amount: Uint128::from(1_u64),
+        },
+    )
+    .unwrap_err();
+    assert_ne!(ContractError::Paused {}, err);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap();
+    assert_eq!(PauseState::default(), query_pause_state(&deps));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &coins(100_000_000, "uusd")),
+        deposit_msg(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn pause_redeems_and_transfers() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    set_guardian(&mut deps);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::Pause {
+            deposits: false,
+            redeems: true,
+            transfers: true,
+            relay: true,
+        },
+    )
+    .unwrap();
+
+    for msg in [
+        ExecuteMsg::Burn {
+            amount: Uint128::from(1_u64),
+        },
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(1_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
+        },
+        ExecuteMsg::Transfer {
+            recipient: "user2".to_string(),
+            amount: Uint128::from(1_u64),
+        },
+        ExecuteMsg::CancelWithdrawal { id: 0 },
+    ] {
+        let err = execute(deps.as_mut(), mock_env(), mock_info("user1", &[]), msg).unwrap_err();
+        assert_eq!(ContractError::Paused {}, err);
+    }
+}
@@ -0,0 +1,161 @@
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{coins, from_binary, OwnedDeps};
+use std::str::FromStr;
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ApyResponse, ExecuteMsg, QueryMsg, RateHistoryResponse};
+use crate::rate_history::{RATE_HISTORY_CAPACITY, RATE_RECORD_INTERVAL};
+use crate::state::RateSnapshot;
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{env_at, instantiate_contract, mock_dependencies};
+
+const DAY: u64 = 24 * 60 * 60;
+
+fn record_rate(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    seconds: u64,
+    exchange_rate: &str,
+) -> Result<(), ContractError> {
+    deps.querier
+        .with_exchange_rate(Decimal256::from_str(exchange_rate).unwrap());
+    execute(
+        deps.as_mut(),
+        env_at(seconds),
+        mock_info("keeper", &[]),
+        ExecuteMsg::RecordRate {},
+    )?;
+    Ok(())
+}
+
+fn query_history(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    start_after: Option<u64>,
+) -> Vec<RateSnapshot> {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::RateHistory {
+            start_after,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let history: RateHistoryResponse = from_binary(&res).unwrap();
+    history.snapshots
+}
+
+fn query_apy(deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) -> ApyResponse {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Apy {}).unwrap();
+    from_binary(&res).unwrap()
+}
+
+#[test]
+fn record_rate_interval() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    record_rate(&mut deps, 0, "1.0").unwrap();
+    let err = record_rate(&mut deps, RATE_RECORD_INTERVAL - 1, "1.1").unwrap_err();
+    assert_eq!(ContractError::RateRecordedRecently {}, err);
+    record_rate(&mut deps, RATE_RECORD_INTERVAL, "1.1").unwrap();
+
+    let start_time = mock_env().block.time.seconds();
+    assert_eq!(
+        vec![
+            RateSnapshot {
+                time: start_time,
+                height: mock_env().block.height,
+                exchange_rate: Decimal256::one(),
+            },
+            RateSnapshot {
+                time: start_time + RATE_RECORD_INTERVAL,
+                height: mock_env().block.height,
+                exchange_rate: Decimal256::from_str("1.1").unwrap(),
+            },
+        ],
+        query_history(&deps, None)
+    );
+    assert_eq!(1, query_history(&deps, Some(start_time)).len());
+    assert_eq!(0, query_history(&deps, Some(u64::MAX)).len());
+}
+
+#[test]
+fn deposit_records_rate() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &coins(100_000_000, "uusd")),
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(1, query_history(&deps, None).len());
+
+    // Recorded recently, deposit still succeeds
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user2", &coins(100_000_000, "uusd")),
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(1, query_history(&deps, None).len());
+}
+
+#[test]
+fn rate_history_capacity() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    for i in 0..=RATE_HISTORY_CAPACITY as u64 {
+        record_rate(&mut deps, i * RATE_RECORD_INTERVAL, "1.0").unwrap();
+    }
+
+    // Oldest snapshot is evicted
+    let start_time = mock_env().block.time.seconds();
+    assert_eq!(
+        start_time + RATE_RECORD_INTERVAL,
+        query_history(&deps, None)[0].time
+    );
+}
+
+#[test]
+fn trailing_apy() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    assert_eq!(
+        ApyResponse {
+            apy_7d: None,
+            apy_30d: None,
+        },
+        query_apy(&deps)
+    );
+
+    record_rate(&mut deps, 0, "1.0").unwrap();
+    record_rate(&mut deps, 23 * DAY, "1.2").unwrap();
+    record_rate(&mut deps, 30 * DAY, "1.212").unwrap();
+
+    // 1% over 7 days, 21.2% over 30 days
+    assert_eq!(
+        ApyResponse {
+            apy_7d: Some(Decimal256::percent(1) * Decimal256::from_ratio(365u64, 7u64)),
+            apy_30d: Some(
+                Decimal256::from_str("0.212").unwrap() * Decimal256::from_ratio(365u64, 30u64)
+            ),
+        },
+        query_apy(&deps)
+    );
+}
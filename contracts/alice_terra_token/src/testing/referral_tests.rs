@@ -0,0 +1,198 @@
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{coins, from_binary, OwnedDeps, Uint128};
+use std::str::FromStr;
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg, ReferralEarningsResponse, ReferralsResponse};
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{
+    deposit_stable, instantiate_contract, mock_dependencies, query_balance,
+};
+
+/// 1% redeem fee, referrers receive 20% of it
+fn referral_dependencies() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            redeem_fee_ratio: Some(Decimal256::from_str("0.01").unwrap()),
+            redeem_fee_tiers: None,
+            deposit_fee_ratio: None,
+            referral_share: Some(Decimal256::from_str("0.2").unwrap()),
+            fee_recipients: None,
+            deposit_caps: None,
+            swap_denoms: None,
+            tax_policy: None,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn query_referral_earnings(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    referrer: &str,
+) -> ReferralEarningsResponse {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::ReferralEarnings {
+            referrer: referrer.to_string(),
+        },
+    )
+    .unwrap();
+    from_binary(&res).unwrap()
+}
+
+#[test]
+fn record_referrals() {
+    let mut deps = referral_dependencies();
+
+    let err =
+        deposit_stable(&mut deps, "user1", 100_000_000, Some("user1"), 100_000_000).unwrap_err();
+    assert_eq!(ContractError::InvalidReferrer {}, err);
+
+    deposit_stable(&mut deps, "user1", 100_000_000, Some("wallet"), 100_000_000).unwrap();
+    deposit_stable(&mut deps, "user2", 100_000_000, Some("wallet"), 200_000_000).unwrap();
+    // First referrer is kept
+    deposit_stable(
+        &mut deps,
+        "user1",
+        100_000_000,
+        Some("other_wallet"),
+        300_000_000,
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Referrals {
+            referrer: "wallet".to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let referrals: ReferralsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        vec!["user1".to_string(), "user2".to_string()],
+        referrals.users
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Referrals {
+            referrer: "wallet".to_string(),
+            start_after: Some("user1".to_string()),
+            limit: None,
+        },
+    )
+    .unwrap();
+    let referrals: ReferralsResponse = from_binary(&res).unwrap();
+    assert_eq!(vec!["user2".to_string()], referrals.users);
+    // Kept referrals are counted once
+    assert_eq!(2, query_referral_earnings(&deps, "wallet").referral_count);
+
+    assert_eq!(
+        ReferralEarningsResponse {
+            referral_count: 0,
+            earnings: Uint128::zero(),
+        },
+        query_referral_earnings(&deps, "other_wallet")
+    );
+}
+
+#[test]
+fn referrer_not_squatted_by_other_sender() {
+    let mut deps = referral_dependencies();
+
+    // Deposit for user1 sent by another account doesn't record its referrer
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("squatter", &coins(1_000_000, "uusd")),
+        ExecuteMsg::DepositStable {
+            recipient: Some("user1".to_string()),
+            min_stable_amount: None,
+            referrer: Some("squatter".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(0, query_referral_earnings(&deps, "squatter").referral_count);
+
+    // user1 can still choose its referrer
+    deposit_stable(&mut deps, "user1", 100_000_000, Some("wallet"), 100_000_000).unwrap();
+    assert_eq!(1, query_referral_earnings(&deps, "wallet").referral_count);
+}
+
+#[test]
+fn authorized_deposit_records_referrer() {
+    let mut deps = referral_dependencies();
+    deps.querier
+        .with_send_authorizations(&[("user1", &[("uusd", 1_000_000_000)])]);
+
+    let deposit_authorized = |deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+                              sender: &str,
+                              referrer: &str| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &coins(1_000_000, "uusd")),
+            ExecuteMsg::DepositStableAuthorized {
+                sender: None,
+                recipient: "user1".to_string(),
+                amount: Uint128::from(1_000_000_u64),
+                referrer: Some(referrer.to_string()),
+            },
+        )
+    };
+
+    // Unauthorized senders can't set it
+    let err = deposit_authorized(&mut deps, "squatter", "squatter").unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+    assert_eq!(0, query_referral_earnings(&deps, "squatter").referral_count);
+
+    // The owner (or an operator) sets the referrer of the recipient
+    deposit_authorized(&mut deps, "owner", "wallet").unwrap();
+    assert_eq!(1, query_referral_earnings(&deps, "wallet").referral_count);
+}
+
+#[test]
+fn referrer_shares_redeem_fees() {
+    let mut deps = referral_dependencies();
+    deposit_stable(&mut deps, "user1", 100_000_000, Some("wallet"), 100_000_000).unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(50_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
+        },
+    )
+    .unwrap();
+
+    // 500,000 ualiceUST fee, 20% to the referrer
+    assert_eq!(Uint128::from(100_000_u64), query_balance(&deps, "wallet"));
+    assert_eq!(Uint128::from(400_000_u64), query_balance(&deps, "owner"));
+    assert_eq!(
+        ReferralEarningsResponse {
+            referral_count: 1,
+            earnings: Uint128::from(100_000_u64),
+        },
+        query_referral_earnings(&deps, "wallet")
+    );
+}
@@ -1,15 +1,13 @@
 use cosmwasm_bignumber::Decimal256;
 use cosmwasm_std::testing::{mock_env, mock_info, MockQuerier, MockStorage};
-use cosmwasm_std::{
-    coins, from_binary, Addr, Coin, ContractResult, DepsMut, Env, OwnedDeps, Reply, Response,
-    SubMsgExecutionResponse, Uint128,
-};
+use cosmwasm_std::{coins, from_binary, Addr, Coin, DepsMut, Env, OwnedDeps, Response, Uint128};
 use cw20::BalanceResponse;
 
 use crate::contract::{execute, instantiate, query, reply};
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, RelayNonceResponse};
 use crate::testing::mock_bech32_api::MockBech32Api;
 use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::OK_DEPOSIT_REPLY;
 
 const CONTRACT_ADDR: &str = "terra1dzhzukyezv0etz22ud940z7adyv7xgcjkahuun";
 const MONEY_MARKET_ADDR: &str = "terra1k82qylhej6lgym9j3w0u4s62pgvyf9c8wypsm7";
@@ -25,16 +23,6 @@ const ACCOUNT_4: &str = "terra1x46rqay4d3cssq8gxxvqz8xt6nwlz4td20k38v";
 const ACCOUNT_4_PUB_KEY: &str =
     "023b33a8524344061b12364cba20fe0a1ab36d4486abf451bb7cebd11ea2241e5b";
 
-const OK_SUBMSG_RESULT: ContractResult<SubMsgExecutionResponse> =
-    ContractResult::Ok(SubMsgExecutionResponse {
-        events: vec![],
-        data: None,
-    });
-const OK_DEPOSIT_REPLY: Reply = Reply {
-    id: 1,
-    result: OK_SUBMSG_RESULT,
-};
-
 pub fn mock_bech32_env() -> Env {
     let mut env = mock_env();
     env.contract.address = Addr::unchecked(CONTRACT_ADDR);
@@ -70,6 +58,16 @@ fn instantiate_bech32_contract(deps: DepsMut) -> (Response, Env) {
         money_market_addr: String::from(MONEY_MARKET_ADDR),
         aterra_token_addr: String::from(ATERRA_TOKEN_ADDR),
         redeem_fee_ratio: Decimal256::zero(),
+        swap_denoms: None,
+        total_deposit_cap: None,
+        address_deposit_cap: None,
+        deposit_fee_ratio: None,
+        fee_recipients: None,
+        redeem_fee_tiers: None,
+        referral_share: None,
+        guardian: None,
+        tax_policy: None,
+        yield_backend: None,
     };
     let env = mock_bech32_env();
     let info = mock_info(ACCOUNT_1, &[]);
@@ -95,6 +93,8 @@ fn relayed_transfer() {
     let info = mock_info(relay_account_addr, &coins(100_000_000, "uusd"));
     let initial_deposit = ExecuteMsg::DepositStable {
         recipient: Some(sender_human_addr.clone()),
+        min_stable_amount: None,
+        referrer: None,
     };
     execute(deps.as_mut(), env.clone(), info, initial_deposit).unwrap();
     deps.querier.with_token_balances(&[(
@@ -170,6 +170,8 @@ fn relayed_transfer_wrong_signature() {
     let info = mock_info(relay_account_addr, &coins(100_000_000, "uusd"));
     let initial_deposit = ExecuteMsg::DepositStable {
         recipient: Some(sender_human_addr.clone()),
+        min_stable_amount: None,
+        referrer: None,
     };
     execute(deps.as_mut(), env.clone(), info, initial_deposit).unwrap();
     deps.querier.with_token_balances(&[(
@@ -219,6 +221,8 @@ fn relayed_transfer_wrong_contract() {
     let info = mock_info(relay_account_addr, &coins(100_000_000, "uusd"));
     let initial_deposit = ExecuteMsg::DepositStable {
         recipient: Some(sender_human_addr.clone()),
+        min_stable_amount: None,
+        referrer: None,
     };
     execute(deps.as_mut(), env.clone(), info, initial_deposit).unwrap();
     deps.querier.with_token_balances(&[(
@@ -268,6 +272,8 @@ fn relayed_transfer_wrong_chain() {
     let info = mock_info(relay_account_addr, &coins(100_000_000, "uusd"));
     let initial_deposit = ExecuteMsg::DepositStable {
         recipient: Some(sender_human_addr.clone()),
+        min_stable_amount: None,
+        referrer: None,
     };
     execute(deps.as_mut(), env.clone(), info, initial_deposit).unwrap();
     deps.querier.with_token_balances(&[(
@@ -317,6 +323,8 @@ fn relayed_transfer_replay() {
     let info = mock_info(relay_account_addr, &coins(100_000_000, "uusd"));
     let initial_deposit = ExecuteMsg::DepositStable {
         recipient: Some(sender_human_addr),
+        min_stable_amount: None,
+        referrer: None,
     };
     execute(deps.as_mut(), env.clone(), info, initial_deposit).unwrap();
     deps.querier.with_token_balances(&[(
@@ -363,6 +371,8 @@ fn relayed_transfer_with_tip() {
     let info = mock_info(relay_account_addr, &coins(100_000_000, "uusd"));
     let initial_deposit = ExecuteMsg::DepositStable {
         recipient: Some(sender_human_addr.clone()),
+        min_stable_amount: None,
+        referrer: None,
     };
     execute(deps.as_mut(), env.clone(), info, initial_deposit).unwrap();
     deps.querier.with_token_balances(&[(
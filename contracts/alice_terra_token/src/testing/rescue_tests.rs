@@ -0,0 +1,96 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    coin, to_binary, BankMsg, CosmosMsg, OwnedDeps, Response, SubMsg, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{instantiate_contract, mock_dependencies};
+
+fn rescue_cw20(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    sender: &str,
+    token: &str,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::RescueCw20 {
+            token: token.to_string(),
+            amount: Uint128::from(1_000_u64),
+            recipient: "user1".to_string(),
+        },
+    )
+}
+
+fn rescue_native(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    sender: &str,
+    denom: &str,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::RescueNative {
+            denom: denom.to_string(),
+            amount: Uint128::from(1_000_u64),
+            recipient: "user1".to_string(),
+        },
+    )
+}
+
+#[test]
+fn rescue_cw20_tokens() {
+    let mut deps = mock_dependencies(&[]);
+    let (_, env) = instantiate_contract(deps.as_mut());
+
+    let err = rescue_cw20(&mut deps, "user1", "random_token_addr").unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    let res = rescue_cw20(&mut deps, "owner", "random_token_addr").unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "random_token_addr".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "user1".to_string(),
+                amount: Uint128::from(1_000_u64),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    // aUST backing & escrowed aliceUST are protected
+    let err = rescue_cw20(&mut deps, "owner", "aterra_token_addr").unwrap_err();
+    assert_eq!(ContractError::ProtectedAsset {}, err);
+    let err = rescue_cw20(&mut deps, "owner", env.contract.address.as_str()).unwrap_err();
+    assert_eq!(ContractError::ProtectedAsset {}, err);
+}
+
+#[test]
+fn rescue_native_tokens() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    let err = rescue_native(&mut deps, "user1", "ukrw").unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    let res = rescue_native(&mut deps, "owner", "ukrw").unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user1".to_string(),
+            amount: vec![coin(1_000, "ukrw")],
+        }))]
+    );
+
+    // Stable denom is always protected
+    let err = rescue_native(&mut deps, "owner", "uusd").unwrap_err();
+    assert_eq!(ContractError::ProtectedAsset {}, err);
+}
@@ -0,0 +1,310 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    attr, coins, from_binary, BankMsg, Decimal, Env, OwnedDeps, Response, SubMsg, Timestamp,
+    Uint128,
+};
+use std::str::FromStr;
+
+use crate::contract::{execute, query, reply};
+use crate::error::ContractError;
+use crate::msg::{DepositCapsMsg, ExecuteMsg, QueryMsg, SchedulesResponse};
+use crate::schedules::MAX_SCHEDULES_PER_OWNER;
+use crate::state::Schedule;
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{
+    env_at, instantiate_contract, mock_dependencies, query_balance, set_aterra_balance,
+    OK_DEPOSIT_REPLY,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    // Tax rate: 0.3%, cap of 1 UST
+    deps.querier.with_tax(
+        Decimal::from_str("0.003").unwrap(),
+        &[(&"uusd".to_string(), &Uint128::from(1_000_000_u64))],
+    );
+    deps
+}
+
+fn create_schedule(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    owner: &str,
+    interval: u64,
+    end: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner, &[]),
+        ExecuteMsg::CreateSchedule {
+            amount: Uint128::from(100_000_000_u64),
+            interval,
+            end,
+        },
+    )
+}
+
+fn process_schedules(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    env: Env,
+    tax_funds: u128,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("keeper", &coins(tax_funds, "uusd")),
+        ExecuteMsg::ProcessSchedules { limit: None },
+    )
+}
+
+fn query_schedules(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    address: &str,
+) -> Vec<Schedule> {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Schedules {
+            address: address.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let res: SchedulesResponse = from_binary(&res).unwrap();
+    res.schedules
+}
+
+fn anchor_deposit_reply(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    env: Env,
+    aterra_balance: u64,
+) {
+    set_aterra_balance(deps, aterra_balance);
+    reply(deps.as_mut(), env, OK_DEPOSIT_REPLY).unwrap();
+}
+
+#[test]
+fn create_schedule_validation() {
+    let mut deps = setup();
+
+    let err = create_schedule(&mut deps, "user1", 60, None).unwrap_err();
+    assert_eq!(ContractError::ScheduleIntervalTooShort {}, err);
+
+    let err = create_schedule(&mut deps, "user1", DAY, Some(mock_env().block.time)).unwrap_err();
+    assert_eq!(ContractError::Expired {}, err);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::CreateSchedule {
+            amount: Uint128::from(999_999_u64),
+            interval: DAY,
+            end: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::ScheduleAmountTooLow {}, err);
+
+    for _ in 0..MAX_SCHEDULES_PER_OWNER {
+        create_schedule(&mut deps, "user1", DAY, None).unwrap();
+    }
+    let err = create_schedule(&mut deps, "user1", DAY, None).unwrap_err();
+    assert_eq!(ContractError::TooManySchedules {}, err);
+
+    for (start_after, count) in [(MAX_SCHEDULES_PER_OWNER as u64 - 2, 1), (u64::MAX, 0)] {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Schedules {
+                address: "user1".to_string(),
+                start_after: Some(start_after),
+                limit: None,
+            },
+        )
+        .unwrap();
+        let res: SchedulesResponse = from_binary(&res).unwrap();
+        assert_eq!(count, res.schedules.len());
+    }
+    create_schedule(&mut deps, "user2", DAY, None).unwrap();
+}
+
+#[test]
+fn process_due_schedules() {
+    let mut deps = setup();
+    // user2 grant is below the schedule amount
+    deps.querier.with_send_authorizations(&[
+        ("user1", &[("uusd", 300_000_000)]),
+        ("user2", &[("uusd", 50_000_000)]),
+    ]);
+    deps.querier.with_base(MockQuerier::new(&[
+        ("user1", &coins(300_000_000, "uusd")),
+        ("user2", &coins(300_000_000, "uusd")),
+    ]));
+
+    let end = mock_env().block.time.plus_seconds(2 * DAY + 1);
+    create_schedule(&mut deps, "user1", DAY, Some(end)).unwrap();
+    create_schedule(&mut deps, "user2", 7 * DAY, None).unwrap();
+
+    // tax of user1 MsgSend & of the Anchor deposit
+    let err = process_schedules(&mut deps, env_at(0), 599_999).unwrap_err();
+    assert_eq!(ContractError::TaxFundsTooLow {}, err);
+
+    let res = process_schedules(&mut deps, env_at(0), 600_000).unwrap();
+    assert_eq!(2, res.messages.len());
+    assert!(res.attributes.contains(&attr("skipped_count", "1")));
+    anchor_deposit_reply(&mut deps, env_at(0), 100_000_000);
+    assert_eq!(
+        Uint128::from(100_000_000_u64),
+        query_balance(&deps, "user1")
+    );
+    assert_eq!(Uint128::zero(), query_balance(&deps, "user2"));
+
+    // Both schedules moved to their next interval
+    assert_eq!(
+        mock_env().block.time.plus_seconds(DAY),
+        query_schedules(&deps, "user1")[0].next_deposit
+    );
+    assert_eq!(
+        mock_env().block.time.plus_seconds(7 * DAY),
+        query_schedules(&deps, "user2")[0].next_deposit
+    );
+    let err = process_schedules(&mut deps, env_at(DAY - 1), 600_000).unwrap_err();
+    assert_eq!(ContractError::NoDueSchedules {}, err);
+
+    // Missed deposits are not caught up, schedule is removed after its end
+    process_schedules(&mut deps, env_at(3 * DAY), 600_000).unwrap();
+    anchor_deposit_reply(&mut deps, env_at(3 * DAY), 200_000_000);
+    assert_eq!(
+        Uint128::from(200_000_000_u64),
+        query_balance(&deps, "user1")
+    );
+    assert_eq!(Vec::<Schedule>::new(), query_schedules(&deps, "user1"));
+    assert_eq!(1, query_schedules(&deps, "user2").len());
+}
+
+#[test]
+fn cancel_schedule() {
+    let mut deps = setup();
+    create_schedule(&mut deps, "user1", DAY, None).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user2", &[]),
+        ExecuteMsg::CancelSchedule { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::CancelSchedule { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(Vec::<Schedule>::new(), query_schedules(&deps, "user1"));
+
+    let err = process_schedules(&mut deps, env_at(0), 600_000).unwrap_err();
+    assert_eq!(ContractError::NoDueSchedules {}, err);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::CancelSchedule { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::ScheduleNotFound {}, err);
+}
+
+#[test]
+fn process_schedules_skips_failing_owners() {
+    let mut deps = setup();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            redeem_fee_ratio: None,
+            redeem_fee_tiers: None,
+            deposit_fee_ratio: None,
+            referral_share: None,
+            fee_recipients: None,
+            deposit_caps: Some(DepositCapsMsg {
+                total_cap: None,
+                address_cap: Some(Uint128::from(150_000_000_u64)),
+            }),
+            swap_denoms: None,
+            tax_policy: None,
+        },
+    )
+    .unwrap();
+    // user2 has no balance, user3 would pass the address cap on its second deposit
+    deps.querier.with_send_authorizations(&[
+        ("user1", &[("uusd", 300_000_000)]),
+        ("user2", &[("uusd", 300_000_000)]),
+        ("user3", &[("uusd", 300_000_000)]),
+    ]);
+    deps.querier.with_base(MockQuerier::new(&[
+        ("user1", &coins(300_000_000, "uusd")),
+        ("user3", &coins(300_000_000, "uusd")),
+    ]));
+
+    create_schedule(&mut deps, "user1", DAY, None).unwrap();
+    create_schedule(&mut deps, "user2", DAY, None).unwrap();
+    create_schedule(&mut deps, "user3", DAY, None).unwrap();
+    create_schedule(&mut deps, "user3", DAY, None).unwrap();
+
+    // Funds beyond the 1.2 UST tax are refunded
+    let res = process_schedules(&mut deps, env_at(0), 2_000_000).unwrap();
+    assert_eq!(3, res.messages.len());
+    assert!(res.attributes.contains(&attr("schedule_count", "4")));
+    assert!(res.attributes.contains(&attr("skipped_count", "2")));
+    assert!(res.attributes.contains(&attr("refund_amount", "800000")));
+    anchor_deposit_reply(&mut deps, env_at(0), 200_000_000);
+    assert_eq!(
+        Uint128::from(100_000_000_u64),
+        query_balance(&deps, "user1")
+    );
+    assert_eq!(Uint128::zero(), query_balance(&deps, "user2"));
+    assert_eq!(
+        Uint128::from(100_000_000_u64),
+        query_balance(&deps, "user3")
+    );
+
+    // Skipped schedules moved to their next interval
+    for schedule in query_schedules(&deps, "user2")
+        .into_iter()
+        .chain(query_schedules(&deps, "user3"))
+    {
+        assert_eq!(
+            mock_env().block.time.plus_seconds(DAY),
+            schedule.next_deposit
+        );
+    }
+}
+
+#[test]
+fn process_skipped_schedules_refunds_funds() {
+    let mut deps = setup();
+    deps.querier
+        .with_send_authorizations(&[("user1", &[("uusd", 300_000_000)])]);
+    create_schedule(&mut deps, "user1", DAY, None).unwrap();
+
+    // user1 has no balance, no tax is needed
+    let res = process_schedules(&mut deps, env_at(0), 600_000).unwrap();
+    assert!(res.attributes.contains(&attr("skipped_count", "1")));
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: "keeper".to_string(),
+            amount: coins(598_205, "uusd"),
+        })]
+    );
+}
@@ -0,0 +1,215 @@
+use cosmwasm_std::testing::{
+    mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{coins, from_binary, Addr, Env, OwnedDeps, StdResult, Uint128};
+use cw20::BalanceResponse;
+use cw20_base::state::{BALANCES, TOKEN_INFO};
+
+use crate::contract::{execute, migrate, query, reply};
+use crate::msg::{ExecuteMsg, MigrateMsg, QueryMsg, TotalSupplyResponse};
+use crate::state::SNAPSHOT_START_HEIGHT;
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{
+    instantiate_contract, mock_dependencies, set_aterra_balance, OK_DEPOSIT_REPLY, OK_REDEEM_REPLY,
+};
+
+/// mock_env advanced by blocks
+fn env_after_blocks(blocks: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height += blocks;
+    env
+}
+
+fn balance_at(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    address: &str,
+    blocks: u64,
+) -> Uint128 {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::BalanceAtHeight {
+            address: address.to_string(),
+            height: env_after_blocks(blocks).block.height,
+        },
+    )
+    .unwrap();
+    let value: BalanceResponse = from_binary(&res).unwrap();
+    value.balance
+}
+
+fn total_supply_at(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    blocks: u64,
+) -> Uint128 {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::TotalSupplyAtHeight {
+            height: env_after_blocks(blocks).block.height,
+        },
+    )
+    .unwrap();
+    let value: TotalSupplyResponse = from_binary(&res).unwrap();
+    value.total_supply
+}
+
+fn transfer(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    blocks: u64,
+    sender: &str,
+    recipient: &str,
+    amount: u64,
+) {
+    execute(
+        deps.as_mut(),
+        env_after_blocks(blocks),
+        mock_info(sender, &[]),
+        ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn balance_and_total_supply_snapshots() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    // Block 1: deposit 100,000,000 uusd for user1
+    execute(
+        deps.as_mut(),
+        env_after_blocks(1),
+        mock_info("user1", &coins(100_000_000, "uusd")),
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+    set_aterra_balance(&mut deps, 100_000_000);
+    reply(deps.as_mut(), env_after_blocks(1), OK_DEPOSIT_REPLY).unwrap();
+
+    // Block 2: transfer twice to user2
+    transfer(&mut deps, 2, "user1", "user2", 30_000_000);
+    transfer(&mut deps, 2, "user1", "user2", 10_000_000);
+
+    // Block 3: user1 redeems 10,000,000 ualiceUST
+    execute(
+        deps.as_mut(),
+        env_after_blocks(3),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(10_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
+        },
+    )
+    .unwrap();
+    deps.querier.with_base(MockQuerier::new(&[(
+        MOCK_CONTRACT_ADDR,
+        &coins(10_000_000, "uusd"),
+    )]));
+    reply(deps.as_mut(), env_after_blocks(3), OK_REDEEM_REPLY).unwrap();
+
+    // Snapshots are taken at the start of each block
+    assert_eq!(Uint128::zero(), balance_at(&deps, "user1", 1));
+    assert_eq!(
+        Uint128::from(100_000_000_u64),
+        balance_at(&deps, "user1", 2)
+    );
+    assert_eq!(Uint128::from(60_000_000_u64), balance_at(&deps, "user1", 3));
+    assert_eq!(Uint128::from(50_000_000_u64), balance_at(&deps, "user1", 4));
+    assert_eq!(Uint128::zero(), balance_at(&deps, "user2", 2));
+    assert_eq!(Uint128::from(40_000_000_u64), balance_at(&deps, "user2", 3));
+
+    assert_eq!(Uint128::zero(), total_supply_at(&deps, 0));
+    assert_eq!(Uint128::zero(), total_supply_at(&deps, 1));
+    assert_eq!(Uint128::from(100_000_000_u64), total_supply_at(&deps, 2));
+    assert_eq!(Uint128::from(100_000_000_u64), total_supply_at(&deps, 3));
+    assert_eq!(Uint128::from(90_000_000_u64), total_supply_at(&deps, 4));
+
+    // No snapshots before instantiation
+    query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::TotalSupplyAtHeight {
+            height: mock_env().block.height - 1,
+        },
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn migrate_from_cw20_base_balances() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    // Legacy contract without snapshots, 50,000,000 ualiceUST held by user1
+    SNAPSHOT_START_HEIGHT.remove(&mut deps.storage);
+    let user1 = Addr::unchecked("user1");
+    BALANCES
+        .save(&mut deps.storage, &user1, &Uint128::from(50_000_000_u64))
+        .unwrap();
+    TOKEN_INFO
+        .update(&mut deps.storage, |mut token_info| -> StdResult<_> {
+            token_info.total_supply = Uint128::from(50_000_000_u64);
+            Ok(token_info)
+        })
+        .unwrap();
+
+    migrate(
+        deps.as_mut(),
+        env_after_blocks(10),
+        MigrateMsg {
+            symbol: None,
+            money_market_addr: None,
+            aterra_token_addr: None,
+            redeem_fee_ratio: None,
+            swap_denoms: None,
+            deposit_fee_ratio: None,
+            fee_recipients: None,
+            guardian: None,
+            tax_policy: None,
+            yield_backend: None,
+        },
+    )
+    .unwrap();
+
+    transfer(&mut deps, 11, "user1", "user2", 20_000_000);
+
+    assert_eq!(
+        Uint128::from(50_000_000_u64),
+        balance_at(&deps, "user1", 10)
+    );
+    assert_eq!(
+        Uint128::from(50_000_000_u64),
+        balance_at(&deps, "user1", 11)
+    );
+    assert_eq!(
+        Uint128::from(30_000_000_u64),
+        balance_at(&deps, "user1", 12)
+    );
+    assert_eq!(
+        Uint128::from(20_000_000_u64),
+        balance_at(&deps, "user2", 12)
+    );
+    assert_eq!(Uint128::from(50_000_000_u64), total_supply_at(&deps, 10));
+    assert_eq!(Uint128::from(50_000_000_u64), total_supply_at(&deps, 12));
+
+    // No snapshots before migration
+    query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::BalanceAtHeight {
+            address: "user1".to_string(),
+            height: env_after_blocks(9).block.height,
+        },
+    )
+    .unwrap_err();
+}
@@ -0,0 +1,104 @@
+use cosmwasm_std::testing::{
+    mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{coins, from_binary, to_binary, CosmosMsg, OwnedDeps, SubMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg, SkimmedTotalResponse, SolvencyResponse};
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{deposit, instantiate_contract, mock_dependencies, set_aterra_balance};
+
+fn query_solvency(deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) -> SolvencyResponse {
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Solvency {}).unwrap();
+    from_binary(&res).unwrap()
+}
+
+#[test]
+fn solvency() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+
+    deps.querier.with_base(MockQuerier::new(&[(
+        MOCK_CONTRACT_ADDR,
+        &coins(5_000_000, "uusd"),
+    )]));
+    assert_eq!(
+        SolvencyResponse {
+            total_supply: Uint128::from(100_000_000_u64),
+            aterra_balance: Uint128::from(100_000_000_u64),
+            stable_reserve: Uint128::from(5_000_000_u64),
+            surplus: Uint128::zero(),
+            deficit: Uint128::zero(),
+        },
+        query_solvency(&deps)
+    );
+
+    set_aterra_balance(&mut deps, 101_000_000);
+    assert_eq!(Uint128::from(1_000_000_u64), query_solvency(&deps).surplus);
+
+    set_aterra_balance(&mut deps, 99_000_000);
+    assert_eq!(Uint128::from(1_000_000_u64), query_solvency(&deps).deficit);
+}
+
+#[test]
+fn deposit_requires_backing() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+
+    // aUST went missing, minting more would leave aliceUST unbacked
+    set_aterra_balance(&mut deps, 50_000_000);
+    let err = deposit(&mut deps, "user2", 150_000_000).unwrap_err();
+    assert_eq!(ContractError::Undercollateralized {}, err);
+}
+
+#[test]
+fn skim_surplus() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+
+    let msg = ExecuteMsg::SkimSurplus {
+        recipient: "treasury".to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    // Fully backed, nothing to skim
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::NoSurplus {}, err);
+
+    set_aterra_balance(&mut deps, 100_000_123);
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "aterra_token_addr".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "treasury".to_string(),
+                amount: Uint128::from(123_u64),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::SkimmedTotal {}).unwrap();
+    let res: SkimmedTotalResponse = from_binary(&res).unwrap();
+    assert_eq!(Uint128::from(123_u64), res.skimmed_total);
+}
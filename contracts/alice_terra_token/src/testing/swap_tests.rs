@@ -0,0 +1,347 @@
+use cosmos_sdk_proto::terra::market::v1beta1::MsgSwap;
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::testing::{mock_env, mock_info, MockQuerier, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, coins, from_binary, to_binary, BankMsg, CosmosMsg, Decimal, DepsMut, Reply, SubMsg,
+    Uint128, WasmMsg,
+};
+use cw20::BalanceResponse;
+use prost::Message;
+use std::str::FromStr;
+
+use crate::anchor::MarketExecuteMsg;
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::execute::{DEPOSIT_STABLE_REPLY_ID, SWAP_DEPOSIT_REPLY_ID, SWAP_REDEEM_REPLY_ID};
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::testing::tests::{
+    mock_dependencies, set_aterra_balance, OK_DEPOSIT_REPLY, OK_REDEEM_REPLY, OK_SUBMSG_RESULT,
+};
+
+const OK_SWAP_REPLY: Reply = Reply {
+    id: SWAP_DEPOSIT_REPLY_ID,
+    result: OK_SUBMSG_RESULT,
+};
+const OK_SWAP_REDEEM_REPLY: Reply = Reply {
+    id: SWAP_REDEEM_REPLY_ID,
+    result: OK_SUBMSG_RESULT,
+};
+
+fn instantiate_swap_contract(deps: DepsMut) {
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        name: String::from("Alice Terra USD"),
+        symbol: String::from("aliceUST"),
+        decimals: 6,
+        stable_denom: String::from("uusd"),
+        money_market_addr: String::from("money_market_addr"),
+        aterra_token_addr: String::from("aterra_token_addr"),
+        redeem_fee_ratio: Decimal256::zero(),
+        swap_denoms: Some(vec!["ukrw".to_string()]),
+        total_deposit_cap: None,
+        address_deposit_cap: None,
+        deposit_fee_ratio: None,
+        fee_recipients: None,
+        redeem_fee_tiers: None,
+        referral_share: None,
+        guardian: None,
+        tax_policy: None,
+        yield_backend: None,
+    };
+    instantiate(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+}
+
+#[test]
+fn instantiate_swap_denoms_contains_stable_denom() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        name: String::from("Alice Terra USD"),
+        symbol: String::from("aliceUST"),
+        decimals: 6,
+        stable_denom: String::from("uusd"),
+        money_market_addr: String::from("money_market_addr"),
+        aterra_token_addr: String::from("aterra_token_addr"),
+        redeem_fee_ratio: Decimal256::zero(),
+        swap_denoms: Some(vec!["ukrw".to_string(), "uusd".to_string()]),
+        total_deposit_cap: None,
+        address_deposit_cap: None,
+        deposit_fee_ratio: None,
+        fee_recipients: None,
+        redeem_fee_tiers: None,
+        referral_share: None,
+        guardian: None,
+        tax_policy: None,
+        yield_backend: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+}
+
+#[test]
+fn swap_deposit() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_swap_contract(deps.as_mut());
+
+    // Deposit 1,000,000,000 ukrw
+    let env = mock_env();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &coins(1_000_000_000, "ukrw")),
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: Some(Uint128::from(800_000_u64)),
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(1, res.messages.len());
+    assert_eq!(SWAP_DEPOSIT_REPLY_ID, res.messages[0].id);
+    match &res.messages[0].msg {
+        CosmosMsg::Stargate { type_url, value } => {
+            assert_eq!("/terra.market.v1beta1.MsgSwap", type_url);
+            let msg_swap = MsgSwap::decode(value.as_slice()).unwrap();
+            assert_eq!(MOCK_CONTRACT_ADDR, msg_swap.trader);
+            assert_eq!("uusd", msg_swap.ask_denom);
+            let offer_coin = msg_swap.offer_coin.unwrap();
+            assert_eq!("ukrw", offer_coin.denom);
+            assert_eq!("1000000000", offer_coin.amount);
+        }
+        _ => panic!("expected MsgSwap"),
+    }
+
+    // Market swap returns 850,000 uusd
+    deps.querier.with_base(MockQuerier::new(&[(
+        MOCK_CONTRACT_ADDR,
+        &coins(850_000, "uusd"),
+    )]));
+    let res = reply(deps.as_mut(), env.clone(), OK_SWAP_REPLY).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "money_market_addr".to_string(),
+                funds: coins(850_000, "uusd"),
+                msg: to_binary(&MarketExecuteMsg::DepositStable {}).unwrap(),
+            }),
+            DEPOSIT_STABLE_REPLY_ID
+        )]
+    );
+
+    // Anchor deposit callback
+    set_aterra_balance(&mut deps, 800_000);
+    let res = reply(deps.as_mut(), env.clone(), OK_DEPOSIT_REPLY).unwrap();
+    assert!(res.attributes.contains(&attr("deposit_denom", "ukrw")));
+    assert!(res
+        .attributes
+        .contains(&attr("deposit_amount", "1000000000")));
+
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::Balance {
+            address: "user1".to_string(),
+        },
+    )
+    .unwrap();
+    let value: BalanceResponse = from_binary(&res).unwrap();
+    assert_eq!(Uint128::from(800_000_u64), value.balance);
+}
+
+#[test]
+fn swap_deposit_below_min_stable_amount() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_swap_contract(deps.as_mut());
+
+    let env = mock_env();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &coins(1_000_000_000, "ukrw")),
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: Some(Uint128::from(900_000_u64)),
+            referrer: None,
+        },
+    )
+    .unwrap();
+
+    // Market swap returns only 850,000 uusd
+    deps.querier.with_base(MockQuerier::new(&[(
+        MOCK_CONTRACT_ADDR,
+        &coins(850_000, "uusd"),
+    )]));
+    let err = reply(deps.as_mut(), env, OK_SWAP_REPLY).unwrap_err();
+    assert_eq!(ContractError::SwapMinStableAmount {}, err);
+}
+
+#[test]
+fn deposit_unsupported_denom() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_swap_contract(deps.as_mut());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &coins(1_000_000_000, "ueur")),
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
+    )
+    .unwrap_err();
+}
+
+/// Deposit 100,000,000 uusd for user1 -> 100,000,000 ualiceUST
+fn deposit_stable_user1(deps: DepsMut) {
+    execute(
+        deps,
+        mock_env(),
+        mock_info("user1", &coins(100_000_000, "uusd")),
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn redeem_to_target_denom() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_swap_contract(deps.as_mut());
+    deposit_stable_user1(deps.as_mut());
+    set_aterra_balance(&mut deps, 100_000_000);
+    reply(deps.as_mut(), mock_env(), OK_DEPOSIT_REPLY).unwrap();
+
+    // Redeem 100,000,000 ualiceUST to ukrw
+    let env = mock_env();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(100_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: Some("ukrw".to_string()),
+            min_target_amount: Some(Uint128::from(110_000_000_000_u64)),
+        },
+    )
+    .unwrap();
+
+    // Anchor redeem callback swaps redeemed uusd to ukrw
+    deps.querier.with_base(MockQuerier::new(&[(
+        MOCK_CONTRACT_ADDR,
+        &coins(100_000_000, "uusd"),
+    )]));
+    let res = reply(deps.as_mut(), env.clone(), OK_REDEEM_REPLY).unwrap();
+    assert_eq!(1, res.messages.len());
+    assert_eq!(SWAP_REDEEM_REPLY_ID, res.messages[0].id);
+    match &res.messages[0].msg {
+        CosmosMsg::Stargate { type_url, value } => {
+            assert_eq!("/terra.market.v1beta1.MsgSwap", type_url);
+            let msg_swap = MsgSwap::decode(value.as_slice()).unwrap();
+            assert_eq!("ukrw", msg_swap.ask_denom);
+            let offer_coin = msg_swap.offer_coin.unwrap();
+            assert_eq!("uusd", offer_coin.denom);
+            assert_eq!("100000000", offer_coin.amount);
+        }
+        _ => panic!("expected MsgSwap"),
+    }
+
+    // Tax rate: 0.3%, cap of 1.4 KRT
+    deps.querier.with_tax(
+        Decimal::from_str("0.003").unwrap(),
+        &[
+            (&"uusd".to_string(), &Uint128::from(1_000_000_u64)),
+            (&"ukrw".to_string(), &Uint128::from(1_400_000_u64)),
+        ],
+    );
+
+    // Market swap returns 120,000,000,000 ukrw, tax is capped in ukrw
+    deps.querier.with_base(MockQuerier::new(&[(
+        MOCK_CONTRACT_ADDR,
+        &coins(120_000_000_000, "ukrw"),
+    )]));
+    let res = reply(deps.as_mut(), env.clone(), OK_SWAP_REDEEM_REPLY).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user1".to_string(),
+            amount: coins(119_998_600_000, "ukrw"),
+        }))]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::Balance {
+            address: "user1".to_string(),
+        },
+    )
+    .unwrap();
+    let value: BalanceResponse = from_binary(&res).unwrap();
+    assert_eq!(Uint128::zero(), value.balance);
+}
+
+#[test]
+fn redeem_to_target_denom_below_min_target_amount() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_swap_contract(deps.as_mut());
+    deposit_stable_user1(deps.as_mut());
+    set_aterra_balance(&mut deps, 100_000_000);
+    reply(deps.as_mut(), mock_env(), OK_DEPOSIT_REPLY).unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(100_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: Some("ukrw".to_string()),
+            min_target_amount: Some(Uint128::from(130_000_000_000_u64)),
+        },
+    )
+    .unwrap();
+    deps.querier.with_base(MockQuerier::new(&[(
+        MOCK_CONTRACT_ADDR,
+        &coins(100_000_000, "uusd"),
+    )]));
+    reply(deps.as_mut(), mock_env(), OK_REDEEM_REPLY).unwrap();
+
+    deps.querier.with_base(MockQuerier::new(&[(
+        MOCK_CONTRACT_ADDR,
+        &coins(120_000_000_000, "ukrw"),
+    )]));
+    let err = reply(deps.as_mut(), mock_env(), OK_SWAP_REDEEM_REPLY).unwrap_err();
+    assert_eq!(ContractError::SwapMinTargetAmount {}, err);
+}
+
+#[test]
+fn redeem_to_unsupported_target_denom() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_swap_contract(deps.as_mut());
+    deposit_stable_user1(deps.as_mut());
+    set_aterra_balance(&mut deps, 100_000_000);
+    reply(deps.as_mut(), mock_env(), OK_DEPOSIT_REPLY).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(100_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: Some("ueur".to_string()),
+            min_target_amount: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::UnsupportedTargetDenom {}, err);
+}
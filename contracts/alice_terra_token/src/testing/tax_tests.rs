@@ -0,0 +1,168 @@
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{coin, Decimal, OwnedDeps, StdError, Uint128};
+use std::str::FromStr;
+
+use crate::contract::{execute, migrate};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, MigrateMsg};
+use crate::state::{config_read, TaxPolicy};
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{instantiate_contract, mock_dependencies};
+use crate::utils::{compute_tax, deduct_tax};
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    // Terra tax rate: 0.3%, cap of 1 UST
+    deps.querier.with_tax(
+        Decimal::from_str("0.003").unwrap(),
+        &[(&"uusd".to_string(), &Uint128::from(1_000_000_u64))],
+    );
+    deps
+}
+
+fn update_tax_policy(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    tax_policy: TaxPolicy,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            redeem_fee_ratio: None,
+            redeem_fee_tiers: None,
+            deposit_fee_ratio: None,
+            referral_share: None,
+            fee_recipients: None,
+            deposit_caps: None,
+            swap_denoms: None,
+            tax_policy: Some(tax_policy),
+        },
+    )?;
+    Ok(())
+}
+
+fn tax(deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>, amount: u128) -> Uint256 {
+    compute_tax(deps.as_ref(), &coin(amount, "uusd")).unwrap()
+}
+
+fn deducted(deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>, amount: u128) -> Uint128 {
+    deduct_tax(deps.as_ref(), coin(amount, "uusd"))
+        .unwrap()
+        .amount
+}
+
+#[test]
+fn terra_tax_policy() {
+    let deps = setup();
+    assert_eq!(
+        TaxPolicy::Terra,
+        config_read(&deps.storage).load().unwrap().tax_policy
+    );
+
+    assert_eq!(Uint256::from(300_000u64), tax(&deps, 100_000_000));
+    assert_eq!(Uint256::from(1_000_000u64), tax(&deps, 1_000_000_000));
+    assert_eq!(Uint128::from(100_000_000u64), deducted(&deps, 100_300_000));
+    assert_eq!(
+        Uint128::from(999_000_000u64),
+        deducted(&deps, 1_000_000_000)
+    );
+}
+
+#[test]
+fn fixed_tax_policy() {
+    let mut deps = setup();
+
+    update_tax_policy(
+        &mut deps,
+        TaxPolicy::Fixed {
+            rate: Decimal256::from_str("0.01").unwrap(),
+            cap: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(Uint256::from(1_000_000u64), tax(&deps, 100_000_000));
+    assert_eq!(Uint256::from(10_000_000u64), tax(&deps, 1_000_000_000));
+    assert_eq!(Uint128::from(100_000_000u64), deducted(&deps, 101_000_000));
+
+    update_tax_policy(
+        &mut deps,
+        TaxPolicy::Fixed {
+            rate: Decimal256::from_str("0.01").unwrap(),
+            cap: Some(Uint128::from(5_000_000u64)),
+        },
+    )
+    .unwrap();
+    assert_eq!(Uint256::from(5_000_000u64), tax(&deps, 1_000_000_000));
+    assert_eq!(
+        Uint128::from(995_000_000u64),
+        deducted(&deps, 1_000_000_000)
+    );
+
+    let err = update_tax_policy(
+        &mut deps,
+        TaxPolicy::Fixed {
+            rate: Decimal256::from_str("1.1").unwrap(),
+            cap: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::Std(StdError::generic_err(
+            "tax_policy rate must be between 0 and 1"
+        )),
+        err
+    );
+}
+
+#[test]
+fn no_tax_policy() {
+    let mut deps = setup();
+    deps.querier
+        .with_send_authorizations(&[("user1", &[("uusd", 100_000_000)])]);
+
+    let msg = ExecuteMsg::DepositStableAuthorized {
+        sender: None,
+        recipient: "user1".to_string(),
+        amount: Uint128::from(100_000_000u64),
+        referrer: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::TaxFundsTooLow {}, err);
+
+    // Set by migration
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            symbol: None,
+            money_market_addr: None,
+            aterra_token_addr: None,
+            redeem_fee_ratio: None,
+            swap_denoms: None,
+            deposit_fee_ratio: None,
+            fee_recipients: None,
+            guardian: None,
+            tax_policy: Some(TaxPolicy::None),
+            yield_backend: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(Uint256::zero(), tax(&deps, 1_000_000_000));
+    assert_eq!(
+        Uint128::from(1_000_000_000u64),
+        deducted(&deps, 1_000_000_000)
+    );
+
+    // No funds needed for tax
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+}
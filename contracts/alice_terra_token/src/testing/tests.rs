@@ -3,8 +3,8 @@ use cosmwasm_std::testing::{
     mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
 };
 use cosmwasm_std::{
-    coins, from_binary, to_binary, BankMsg, Coin, ContractResult, CosmosMsg, Decimal, Reply,
-    SubMsg, SubMsgExecutionResponse, WasmMsg,
+    coins, from_binary, to_binary, BankMsg, Coin, ContractResult, CosmosMsg, Decimal, Event, Reply,
+    StdError, SubMsg, SubMsgExecutionResponse, WasmMsg,
 };
 use cosmwasm_std::{DepsMut, OwnedDeps, Response};
 use cosmwasm_std::{Env, Uint128};
@@ -15,24 +15,25 @@ use crate::anchor::{MarketCw20HookMsg, MarketExecuteMsg};
 use crate::contract::query;
 use crate::contract::{execute, reply};
 use crate::contract::{instantiate, migrate};
+use crate::error::ContractError;
 use crate::execute::{DEPOSIT_STABLE_REPLY_ID, REDEEM_STABLE_REPLY_ID};
 use crate::msg::InstantiateMsg;
-use crate::msg::QueryMsg;
+use crate::msg::{EarningsResponse, QueryMsg};
 use crate::msg::{ExecuteMsg, MigrateMsg};
 use crate::state::Config;
 
 use crate::testing::mock_querier::WasmMockQuerier;
 
-const OK_SUBMSG_RESULT: ContractResult<SubMsgExecutionResponse> =
+pub const OK_SUBMSG_RESULT: ContractResult<SubMsgExecutionResponse> =
     ContractResult::Ok(SubMsgExecutionResponse {
         events: vec![],
         data: None,
     });
-const OK_DEPOSIT_REPLY: Reply = Reply {
+pub const OK_DEPOSIT_REPLY: Reply = Reply {
     id: DEPOSIT_STABLE_REPLY_ID,
     result: OK_SUBMSG_RESULT,
 };
-const OK_REDEEM_REPLY: Reply = Reply {
+pub const OK_REDEEM_REPLY: Reply = Reply {
     id: REDEEM_STABLE_REPLY_ID,
     result: OK_SUBMSG_RESULT,
 };
@@ -56,7 +57,7 @@ pub fn mock_dependencies(
     }
 }
 
-fn instantiate_contract(deps: DepsMut) -> (Response, Env) {
+pub fn instantiate_contract(deps: DepsMut) -> (Response, Env) {
     let msg = InstantiateMsg {
         owner: "owner".to_string(),
         name: String::from("Alice Terra USD"),
@@ -66,6 +67,16 @@ fn instantiate_contract(deps: DepsMut) -> (Response, Env) {
         money_market_addr: String::from("money_market_addr"),
         aterra_token_addr: String::from("aterra_token_addr"),
         redeem_fee_ratio: Decimal256::zero(),
+        swap_denoms: None,
+        total_deposit_cap: None,
+        address_deposit_cap: None,
+        deposit_fee_ratio: None,
+        fee_recipients: None,
+        redeem_fee_tiers: None,
+        referral_share: None,
+        guardian: None,
+        tax_policy: None,
+        yield_backend: None,
     };
     let env = mock_env();
     let info = mock_info("owner", &[]);
@@ -76,6 +87,96 @@ fn instantiate_contract(deps: DepsMut) -> (Response, Env) {
     (res, env)
 }
 
+/// Env at seconds after mock_env
+pub fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+pub fn set_aterra_balance(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    balance: u64,
+) {
+    deps.querier.with_token_balances(&[(
+        &"aterra_token_addr".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(balance))],
+    )]);
+}
+
+pub fn set_stable_balance(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    balance: u128,
+) {
+    deps.querier.with_base(MockQuerier::new(&[(
+        MOCK_CONTRACT_ADDR,
+        &coins(balance, "uusd"),
+    )]));
+}
+
+/// Deposit stable_amount uusd for sender, Anchor returns aterra_balance in total
+pub fn deposit_stable(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    sender: &str,
+    stable_amount: u128,
+    referrer: Option<&str>,
+    aterra_balance: u64,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &coins(stable_amount, "uusd")),
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: referrer.map(|referrer| referrer.to_string()),
+        },
+    )?;
+    set_aterra_balance(deps, aterra_balance);
+    reply(deps.as_mut(), mock_env(), OK_DEPOSIT_REPLY)?;
+    Ok(())
+}
+
+/// Deposit 100,000,000 uusd for sender, Anchor returns aterra_balance in total
+pub fn deposit(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    sender: &str,
+    aterra_balance: u64,
+) -> Result<(), ContractError> {
+    deposit_stable(deps, sender, 100_000_000, None, aterra_balance)
+}
+
+pub fn query_balance(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    address: &str,
+) -> Uint128 {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance {
+            address: address.to_string(),
+        },
+    )
+    .unwrap();
+    let value: BalanceResponse = from_binary(&res).unwrap();
+    value.balance
+}
+
+pub fn query_earnings(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    address: &str,
+) -> EarningsResponse {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Earnings {
+            address: address.to_string(),
+        },
+    )
+    .unwrap();
+    from_binary(&res).unwrap()
+}
+
 #[test]
 fn basic_instantiation() {
     let mut deps = mock_dependencies(&[]);
@@ -99,6 +200,16 @@ fn instantiate_redeem_fee_ratio_greater_than_one() {
         money_market_addr: String::from("money_market_addr"),
         aterra_token_addr: String::from("aterra_token_addr"),
         redeem_fee_ratio: Decimal256::from_str("1.1").unwrap(), // greater than 1
+        swap_denoms: None,
+        total_deposit_cap: None,
+        address_deposit_cap: None,
+        deposit_fee_ratio: None,
+        fee_recipients: None,
+        redeem_fee_tiers: None,
+        referral_share: None,
+        guardian: None,
+        tax_policy: None,
+        yield_backend: None,
     };
     let env = mock_env();
     let info = mock_info("owner", &[]);
@@ -117,10 +228,15 @@ fn basic_migration() {
         env.clone(),
         MigrateMsg {
             symbol: Some("newToken".to_string()),
-            owner: None,
             money_market_addr: None,
             aterra_token_addr: None,
             redeem_fee_ratio: Some(Decimal256::from_str("0.12345").unwrap()),
+            swap_denoms: None,
+            deposit_fee_ratio: None,
+            fee_recipients: None,
+            guardian: None,
+            tax_policy: None,
+            yield_backend: None,
         },
     )
     .unwrap();
@@ -138,6 +254,73 @@ fn basic_migration() {
     assert_eq!(token_info.symbol, "newToken".to_string());
 }
 
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let (_res, env) = instantiate_contract(deps.as_mut());
+
+    let msg = ExecuteMsg::UpdateConfig {
+        redeem_fee_ratio: Some(Decimal256::from_str("0.001").unwrap()),
+        redeem_fee_tiers: None,
+        deposit_fee_ratio: None,
+        referral_share: None,
+        fee_recipients: None,
+        deposit_caps: None,
+        swap_denoms: Some(vec!["ukrw".to_string()]),
+        tax_policy: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user1", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        res.events,
+        vec![Event::new("update_config")
+            .add_attribute("old_redeem_fee_ratio", "0")
+            .add_attribute("new_redeem_fee_ratio", "0.001")
+            .add_attribute("old_swap_denoms", "")
+            .add_attribute("new_swap_denoms", "ukrw")]
+    );
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: Config = from_binary(&res).unwrap();
+    assert_eq!(
+        config.redeem_fee_ratio,
+        Decimal256::from_str("0.001").unwrap()
+    );
+    assert_eq!(config.swap_denoms, vec!["ukrw".to_string()]);
+
+    // Validated by save_config
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateConfig {
+            redeem_fee_ratio: None,
+            redeem_fee_tiers: None,
+            deposit_fee_ratio: Some(Decimal256::from_str("1.1").unwrap()),
+            referral_share: None,
+            fee_recipients: None,
+            deposit_caps: None,
+            swap_denoms: None,
+            tax_policy: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::Std(StdError::generic_err(
+            "deposit_fee_ratio must be between 0 and 1"
+        )),
+        err
+    );
+}
+
 #[test]
 fn basic_deposit() {
     let mut deps = mock_dependencies(&[]);
@@ -150,7 +333,11 @@ fn basic_deposit() {
         deps.as_mut(),
         env.clone(),
         info,
-        ExecuteMsg::DepositStable { recipient: None },
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
     )
     .unwrap();
     assert_eq!(
@@ -201,7 +388,11 @@ fn deposit_zero() {
         deps.as_mut(),
         env,
         info,
-        ExecuteMsg::DepositStable { recipient: None },
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
     )
     .unwrap_err();
 }
@@ -216,17 +407,20 @@ fn deposit_authorized() {
         Decimal::from_str("0.003").unwrap(),
         &[(&"uusd".to_string(), &Uint128::from(1_000_000_u64))],
     );
+    deps.querier
+        .with_send_authorizations(&[("user1", &[("uusd", 100_000_000)])]);
 
     // Deposit 100,000,000 uusd
     let env = mock_env();
     let res = execute(
         deps.as_mut(),
         env.clone(),
-        mock_info("owner", &coins(0_600_000, "uusd")),
+        mock_info("owner", &coins(600_000, "uusd")),
         ExecuteMsg::DepositStableAuthorized {
             sender: Some("user1".to_string()),
             recipient: "user1".to_string(),
             amount: Uint128::from(100_000_000_u64),
+            referrer: None,
         },
     )
     .unwrap();
@@ -276,6 +470,8 @@ fn deposit_authorized_tax_funds_too_low() {
         Decimal::from_str("0.003").unwrap(),
         &[(&"uusd".to_string(), &Uint128::from(1_000_000_u64))],
     );
+    deps.querier
+        .with_send_authorizations(&[("user1", &[("uusd", 100_000_000)])]);
 
     // Deposit 100,000,000 uusd
     // Send 0.599 UST funds, not enough for tax
@@ -283,16 +479,74 @@ fn deposit_authorized_tax_funds_too_low() {
     execute(
         deps.as_mut(),
         env.clone(),
-        mock_info("owner", &coins(0_599_000, "uusd")),
+        mock_info("owner", &coins(599_000, "uusd")),
         ExecuteMsg::DepositStableAuthorized {
             sender: Some("user1".to_string()),
             recipient: "user1".to_string(),
             amount: Uint128::from(100_000_000_u64),
+            referrer: None,
         },
     )
     .unwrap_err();
 }
 
+#[test]
+fn deposit_authorized_spend_limit_too_low() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    let msg = ExecuteMsg::DepositStableAuthorized {
+        sender: None,
+        recipient: "user1".to_string(),
+        amount: Uint128::from(100_000_000_u64),
+        referrer: None,
+    };
+
+    // No grant
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &coins(600_000, "uusd")),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::InsufficientSendAuthorization {}, err);
+
+    // Grant below amount, or in another denom
+    deps.querier
+        .with_send_authorizations(&[("user1", &[("uusd", 99_999_999), ("ukrw", 100_000_000)])]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &coins(600_000, "uusd")),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::InsufficientSendAuthorization {}, err);
+}
+
+#[test]
+fn deposit_authorized_generic_grant() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    // GenericAuthorization for MsgSend has no spend limit
+    deps.querier.with_generic_send_authorizations(&["user1"]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &coins(600_000, "uusd")),
+        ExecuteMsg::DepositStableAuthorized {
+            sender: None,
+            recipient: "user1".to_string(),
+            amount: Uint128::from(100_000_000_u64),
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(2, res.messages.len());
+}
+
 #[test]
 fn basic_redeem() {
     let mut deps = mock_dependencies(&[]);
@@ -305,7 +559,11 @@ fn basic_redeem() {
         deps.as_mut(),
         env.clone(),
         info,
-        ExecuteMsg::DepositStable { recipient: None },
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
     )
     .unwrap();
     deps.querier.with_token_balances(&[(
@@ -327,6 +585,9 @@ fn basic_redeem() {
         ExecuteMsg::RedeemStable {
             recipient: None,
             burn_amount: Uint128::from(100_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
         },
     )
     .unwrap();
@@ -391,7 +652,11 @@ fn redeem_after_interest() {
         deps.as_mut(),
         env.clone(),
         mock_info("user1", &coins(100_000_000, "uusd")),
-        ExecuteMsg::DepositStable { recipient: None },
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
     )
     .unwrap();
     deps.querier.with_token_balances(&[(
@@ -423,6 +688,9 @@ fn redeem_after_interest() {
         ExecuteMsg::RedeemStable {
             recipient: None,
             burn_amount: Uint128::from(100_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
         },
     )
     .unwrap();
@@ -487,7 +755,11 @@ fn redeem_after_interest_balance_too_low() {
         deps.as_mut(),
         env.clone(),
         mock_info("user1", &coins(100_000_000, "uusd")),
-        ExecuteMsg::DepositStable { recipient: None },
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
     )
     .unwrap();
 
@@ -504,6 +776,9 @@ fn redeem_after_interest_balance_too_low() {
         ExecuteMsg::RedeemStable {
             recipient: None,
             burn_amount: Uint128::from(100_000_001_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
         },
     )
     .unwrap_err();
@@ -523,6 +798,16 @@ fn redeem_after_interest_with_fee() {
         money_market_addr: String::from("money_market_addr"),
         aterra_token_addr: String::from("aterra_token_addr"),
         redeem_fee_ratio: Decimal256::from_str("0.005").unwrap(),
+        swap_denoms: None,
+        total_deposit_cap: None,
+        address_deposit_cap: None,
+        deposit_fee_ratio: None,
+        fee_recipients: None,
+        redeem_fee_tiers: None,
+        referral_share: None,
+        guardian: None,
+        tax_policy: None,
+        yield_backend: None,
     };
     let env = mock_env();
     let info = mock_info("owner", &[]);
@@ -534,7 +819,11 @@ fn redeem_after_interest_with_fee() {
         deps.as_mut(),
         env.clone(),
         mock_info("user1", &coins(100_000_000, "uusd")),
-        ExecuteMsg::DepositStable { recipient: None },
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
     )
     .unwrap();
     deps.querier.with_token_balances(&[(
@@ -566,6 +855,9 @@ fn redeem_after_interest_with_fee() {
         ExecuteMsg::RedeemStable {
             recipient: None,
             burn_amount: Uint128::from(100_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
         },
     )
     .unwrap();
@@ -0,0 +1,298 @@
+use cosmwasm_std::testing::{
+    mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, BankMsg, Coin, ContractResult, CosmosMsg, OwnedDeps, Reply,
+    StdError, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, TokenInfoResponse};
+
+use crate::anchor::MarketCw20HookMsg;
+use crate::contract::{execute, query, reply};
+use crate::error::ContractError;
+use crate::execute::{PROCESS_WITHDRAWALS_REPLY_ID, REDEEM_STABLE_REPLY_ID};
+use crate::msg::{ExecuteMsg, QueryMsg, WithdrawalTicketsResponse};
+use crate::testing::mock_querier::WasmMockQuerier;
+use crate::testing::tests::{
+    deposit, instantiate_contract, mock_dependencies, query_balance, OK_SUBMSG_RESULT,
+};
+
+fn anchor_redeem_error_reply() -> Reply {
+    Reply {
+        id: REDEEM_STABLE_REPLY_ID,
+        result: ContractResult::Err(
+            "Not enough uusd available; borrow demand too high".to_string(),
+        ),
+    }
+}
+
+/// Deposit 100,000,000 uusd for user1 -> 100,000,000 ualiceUST
+fn deposit_user1() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+
+    deposit(&mut deps, "user1", 100_000_000).unwrap();
+
+    deps
+}
+
+fn query_tickets(
+    deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    address: &str,
+) -> WithdrawalTicketsResponse {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::WithdrawalTickets {
+            address: address.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    from_binary(&res).unwrap()
+}
+
+fn queue_redeem(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    sender: &str,
+    burn_amount: u64,
+) {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(burn_amount),
+            queue_if_illiquid: Some(true),
+            target_denom: None,
+            min_target_amount: None,
+        },
+    )
+    .unwrap();
+    reply(deps.as_mut(), mock_env(), anchor_redeem_error_reply()).unwrap();
+}
+
+#[test]
+fn redeem_illiquid_without_queue_fails() {
+    let mut deps = deposit_user1();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(40_000_000_u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
+        },
+    )
+    .unwrap();
+    reply(deps.as_mut(), mock_env(), anchor_redeem_error_reply()).unwrap_err();
+}
+
+#[test]
+fn redeem_other_error_not_queued() {
+    let mut deps = deposit_user1();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(40_000_000_u64),
+            queue_if_illiquid: Some(true),
+            target_denom: None,
+            min_target_amount: None,
+        },
+    )
+    .unwrap();
+    let err = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: REDEEM_STABLE_REPLY_ID,
+            result: ContractResult::Err("Unauthorized".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::Std(StdError::generic_err("Unauthorized")),
+        err
+    );
+}
+
+#[test]
+fn query_tickets_start_after() {
+    let mut deps = deposit_user1();
+    queue_redeem(&mut deps, "user1", 10_000_000);
+    queue_redeem(&mut deps, "user1", 10_000_000);
+
+    let query_ids = |start_after: u64| -> Vec<u64> {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::WithdrawalTickets {
+                address: "user1".to_string(),
+                start_after: Some(start_after),
+                limit: None,
+            },
+        )
+        .unwrap();
+        let res: WithdrawalTicketsResponse = from_binary(&res).unwrap();
+        res.tickets.iter().map(|ticket| ticket.id).collect()
+    };
+    assert_eq!(vec![1], query_ids(0));
+    assert_eq!(Vec::<u64>::new(), query_ids(u64::MAX));
+}
+
+#[test]
+fn queue_and_process_withdrawals() {
+    let mut deps = deposit_user1();
+
+    // Transfer 10,000,000 ualiceUST to user2, both queue a withdrawal
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::Transfer {
+            recipient: "user2".to_string(),
+            amount: Uint128::from(10_000_000_u64),
+        },
+    )
+    .unwrap();
+    queue_redeem(&mut deps, "user1", 30_000_000);
+    queue_redeem(&mut deps, "user2", 10_000_000);
+
+    // aliceUST is escrowed by the contract
+    assert_eq!(Uint128::from(60_000_000_u64), query_balance(&deps, "user1"));
+    assert_eq!(Uint128::zero(), query_balance(&deps, "user2"));
+    assert_eq!(
+        Uint128::from(40_000_000_u64),
+        query_balance(&deps, MOCK_CONTRACT_ADDR)
+    );
+
+    let tickets = query_tickets(&deps, "user1").tickets;
+    assert_eq!(1, tickets.len());
+    assert_eq!(0, tickets[0].id);
+    assert_eq!(Uint128::from(30_000_000_u64), tickets[0].burn_amount);
+    assert_eq!(1, query_tickets(&deps, "user2").tickets[0].id);
+
+    // Anyone can process the queue once liquidity returns
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ProcessWithdrawals { limit: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "aterra_token_addr".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "money_market_addr".to_string(),
+                    amount: Uint128::from(40_000_000_u64),
+                    msg: to_binary(&MarketCw20HookMsg::RedeemStable {}).unwrap(),
+                })
+                .unwrap(),
+            }),
+            PROCESS_WITHDRAWALS_REPLY_ID
+        )]
+    );
+    deps.querier.with_base(MockQuerier::new(&[(
+        MOCK_CONTRACT_ADDR,
+        &coins(40_000_400, "uusd"),
+    )]));
+
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: PROCESS_WITHDRAWALS_REPLY_ID,
+            result: OK_SUBMSG_RESULT,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "user1".to_string(),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::from(30_000_300_u64),
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "user2".to_string(),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::from(10_000_100_u64),
+                }],
+            })),
+        ]
+    );
+
+    // Tickets fulfilled, escrow burned
+    assert!(query_tickets(&deps, "user1").tickets.is_empty());
+    assert!(query_tickets(&deps, "user2").tickets.is_empty());
+    assert_eq!(Uint128::zero(), query_balance(&deps, MOCK_CONTRACT_ADDR));
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::TokenInfo {}).unwrap();
+    let token_info: TokenInfoResponse = from_binary(&res).unwrap();
+    assert_eq!(Uint128::from(60_000_000_u64), token_info.total_supply);
+
+    // Queue is now empty
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ProcessWithdrawals { limit: None },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::WithdrawalQueueEmpty {}, err);
+}
+
+#[test]
+fn cancel_withdrawal() {
+    let mut deps = deposit_user1();
+    queue_redeem(&mut deps, "user1", 40_000_000);
+
+    // Only the ticket owner can cancel
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user2", &[]),
+        ExecuteMsg::CancelWithdrawal { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::CancelWithdrawal { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(
+        Uint128::from(100_000_000_u64),
+        query_balance(&deps, "user1")
+    );
+    assert!(query_tickets(&deps, "user1").tickets.is_empty());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::CancelWithdrawal { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::WithdrawalTicketNotFound {}, err);
+}
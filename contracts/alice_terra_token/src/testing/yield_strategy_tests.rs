@@ -0,0 +1,207 @@
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Reply, SubMsg, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use std::str::FromStr;
+
+use crate::anchor::{MarketCw20HookMsg, MarketExecuteMsg};
+use crate::contract::{execute, query, reply};
+use crate::execute::{DEPOSIT_STABLE_REPLY_ID, EMERGENCY_EXIT_REPLY_ID, REDEEM_STABLE_REPLY_ID};
+use crate::msg::{EmergencyExitResponse, ExecuteMsg, QueryMsg};
+use crate::state::{config_read, YieldBackend};
+use crate::testing::mock_strategy::{
+    mock_redeem_msg, set_mock_exchange_rate, set_mock_position, use_mock_strategy, MockStrategy,
+    MOCK_MARKET_ADDR,
+};
+use crate::testing::tests::{
+    instantiate_contract, mock_dependencies, query_balance, set_stable_balance, OK_DEPOSIT_REPLY,
+    OK_REDEEM_REPLY, OK_SUBMSG_RESULT,
+};
+use crate::yield_strategy::{load_yield_strategy, YieldStrategy};
+
+#[test]
+fn position_value() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    set_mock_position(&mut deps.storage, 100_000_000);
+    set_mock_exchange_rate(&mut deps.storage, Decimal256::from_str("1.25").unwrap());
+    assert_eq!(
+        Uint128::from(125_000_000u64),
+        MockStrategy::new(&config_read(&deps.storage).load().unwrap())
+            .query_position_value(deps.as_ref(), Addr::unchecked(MOCK_CONTRACT_ADDR), 12_345)
+            .unwrap()
+    );
+}
+
+#[test]
+fn mock_backend_deposit_redeem_exit() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    use_mock_strategy();
+
+    // Deposit into the mock market, minted from the position received
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &coins(100_000_000, "uusd")),
+        ExecuteMsg::DepositStable {
+            recipient: None,
+            min_stable_amount: None,
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: MOCK_MARKET_ADDR.to_string(),
+                amount: coins(100_000_000, "uusd"),
+            }),
+            DEPOSIT_STABLE_REPLY_ID
+        )]
+    );
+    set_mock_position(&mut deps.storage, 80_000_000);
+    reply(deps.as_mut(), mock_env(), OK_DEPOSIT_REPLY).unwrap();
+    assert_eq!(Uint128::from(80_000_000u64), query_balance(&deps, "user1"));
+
+    // Redeem from the mock market, pays the stablecoins received
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user1", &[]),
+        ExecuteMsg::RedeemStable {
+            recipient: None,
+            burn_amount: Uint128::from(40_000_000u64),
+            queue_if_illiquid: None,
+            target_denom: None,
+            min_target_amount: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            mock_redeem_msg(40_000_000),
+            REDEEM_STABLE_REPLY_ID
+        )]
+    );
+    set_mock_position(&mut deps.storage, 40_000_000);
+    set_stable_balance(&mut deps, 50_000_000);
+    let res = reply(deps.as_mut(), mock_env(), OK_REDEEM_REPLY).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user1".to_string(),
+            amount: coins(50_000_000, "uusd"),
+        }))]
+    );
+    assert_eq!(Uint128::from(40_000_000u64), query_balance(&deps, "user1"));
+
+    // Emergency exit redeems the whole mock position
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            mock_redeem_msg(40_000_000),
+            EMERGENCY_EXIT_REPLY_ID
+        )]
+    );
+    set_mock_position(&mut deps.storage, 0);
+    set_stable_balance(&mut deps, 110_000_000);
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: EMERGENCY_EXIT_REPLY_ID,
+            result: OK_SUBMSG_RESULT,
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::EmergencyExit {}).unwrap();
+    let exit = from_binary::<EmergencyExitResponse>(&res)
+        .unwrap()
+        .exit
+        .unwrap();
+    assert_eq!(Uint128::from(40_000_000u64), exit.aterra_amount);
+    assert_eq!(Uint128::from(60_000_000u64), exit.stable_amount);
+}
+
+#[test]
+fn anchor_strategy() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_contract(deps.as_mut());
+    assert_eq!(
+        YieldBackend::Anchor,
+        config_read(&deps.storage).load().unwrap().yield_backend
+    );
+
+    deps.querier.with_token_balances(&[(
+        &"aterra_token_addr".to_string(),
+        &[(
+            &MOCK_CONTRACT_ADDR.to_string(),
+            &Uint128::from(100_000_000u64),
+        )],
+    )]);
+    deps.querier
+        .with_exchange_rate(Decimal256::from_str("1.1").unwrap());
+
+    let strategy = load_yield_strategy(&deps.storage).unwrap();
+    let contract = Addr::unchecked(MOCK_CONTRACT_ADDR);
+    assert_eq!(
+        Uint128::from(100_000_000u64),
+        strategy
+            .query_position(deps.as_ref(), contract.clone())
+            .unwrap()
+    );
+    assert_eq!(
+        Uint128::from(110_000_000u64),
+        strategy
+            .query_position_value(deps.as_ref(), contract, mock_env().block.height)
+            .unwrap()
+    );
+
+    let res = strategy
+        .deposit_stable(Uint128::from(100_000_000u64), 1)
+        .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "money_market_addr".to_string(),
+                funds: coins(100_000_000, "uusd"),
+                msg: to_binary(&MarketExecuteMsg::DepositStable {}).unwrap(),
+            }),
+            1
+        )]
+    );
+
+    let res = strategy
+        .redeem_stable(Uint128::from(50_000_000u64), 2)
+        .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_always(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "aterra_token_addr".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "money_market_addr".to_string(),
+                    amount: Uint128::from(50_000_000u64),
+                    msg: to_binary(&MarketCw20HookMsg::RedeemStable {}).unwrap(),
+                })
+                .unwrap(),
+            }),
+            2
+        )]
+    );
+}
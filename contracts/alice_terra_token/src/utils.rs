@@ -2,24 +2,43 @@ use cosmwasm_bignumber::{Decimal256, Uint256};
 use cosmwasm_std::{Coin, Deps, StdResult};
 
 use crate::error::ContractError;
+use crate::state::{config_read, TaxPolicy};
 
 use terra_cosmwasm::TerraQuerier;
 
+/// Tax rate & cap of denom under the configured tax policy, none is uncapped
+fn tax_rate_and_cap(deps: Deps, denom: &str) -> StdResult<(Decimal256, Option<Uint256>)> {
+    let config = config_read(deps.storage).load()?;
+    match config.tax_policy {
+        TaxPolicy::Terra => {
+            let terra_querier = TerraQuerier::new(&deps.querier);
+            let tax_rate = Decimal256::from((terra_querier.query_tax_rate()?).rate);
+            let tax_cap = Uint256::from((terra_querier.query_tax_cap(denom.to_string())?).cap);
+            Ok((tax_rate, Some(tax_cap)))
+        }
+        TaxPolicy::Fixed { rate, cap } => Ok((rate, cap.map(Uint256::from))),
+        TaxPolicy::None => Ok((Decimal256::zero(), None)),
+    }
+}
+
+fn apply_cap(tax_amount: Uint256, tax_cap: Option<Uint256>) -> Uint256 {
+    match tax_cap {
+        Some(tax_cap) => std::cmp::min(tax_amount, tax_cap),
+        None => tax_amount,
+    }
+}
+
 pub fn compute_tax(deps: Deps, coin: &Coin) -> StdResult<Uint256> {
-    let terra_querier = TerraQuerier::new(&deps.querier);
-    let tax_rate = Decimal256::from((terra_querier.query_tax_rate()?).rate);
-    let tax_cap = Uint256::from((terra_querier.query_tax_cap(coin.denom.to_string())?).cap);
+    let (tax_rate, tax_cap) = tax_rate_and_cap(deps, &coin.denom)?;
     let amount = Uint256::from(coin.amount);
-    Ok(std::cmp::min(amount * tax_rate, tax_cap))
+    Ok(apply_cap(amount * tax_rate, tax_cap))
 }
 
 // https://github.com/Anchor-Protocol/money-market-contracts/blob/230ccf7f41fb04fff66536c48a9d397225813544/packages/moneymarket/src/querier.rs#L70
 fn compute_deducted_tax(deps: Deps, coin: &Coin) -> StdResult<Uint256> {
-    let terra_querier = TerraQuerier::new(&deps.querier);
-    let tax_rate = Decimal256::from((terra_querier.query_tax_rate()?).rate);
-    let tax_cap = Uint256::from((terra_querier.query_tax_cap(coin.denom.to_string())?).cap);
+    let (tax_rate, tax_cap) = tax_rate_and_cap(deps, &coin.denom)?;
     let amount = Uint256::from(coin.amount);
-    Ok(std::cmp::min(
+    Ok(apply_cap(
         amount * Decimal256::one() - amount / (Decimal256::one() + tax_rate),
         tax_cap,
     ))
@@ -0,0 +1,220 @@
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, ContractResult, CosmosMsg, DepsMut, Env, MessageInfo, Order, Response,
+    StdError, StdResult, SubMsgExecutionResponse, Uint128,
+};
+
+use crate::earnings::record_withdrawal;
+use crate::emergency_exit::assert_not_exited;
+use crate::error::ContractError;
+use crate::execute::PROCESS_WITHDRAWALS_REPLY_ID;
+use crate::query::query_native_balance;
+use crate::snapshot::{execute_burn, execute_transfer};
+use crate::state::{
+    config_read, next_withdrawal_ticket_id_mut, pending_process_withdrawals_mut,
+    withdrawal_ticket_owners_mut, withdrawal_tickets_mut, withdrawal_tickets_read,
+    PendingProcessWithdrawals, WithdrawalTicket,
+};
+use crate::utils::deduct_tax;
+use crate::yield_strategy::yield_strategy;
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+/// Escrow burn_amount aliceUST from owner into a new withdrawal ticket at the end of the queue
+pub fn enqueue_withdrawal(
+    mut deps: DepsMut,
+    env: Env,
+    owner: Addr,
+    recipient: Addr,
+    burn_amount: Uint128,
+) -> Result<WithdrawalTicket, ContractError> {
+    execute_transfer(
+        deps.branch(),
+        env.clone(),
+        MessageInfo {
+            sender: owner.clone(),
+            funds: vec![],
+        },
+        env.contract.address.to_string(),
+        burn_amount,
+    )?;
+
+    let mut next_id = next_withdrawal_ticket_id_mut(deps.storage);
+    let id = next_id.may_load()?.unwrap_or_default();
+    next_id.save(&(id + 1))?;
+
+    let ticket = WithdrawalTicket {
+        id,
+        owner,
+        recipient,
+        burn_amount,
+        created_at: env.block.time,
+    };
+    withdrawal_tickets_mut(deps.storage).save(&id.to_be_bytes(), &ticket)?;
+    withdrawal_ticket_owners_mut(deps.storage, &ticket.owner).save(&id.to_be_bytes(), &true)?;
+
+    Ok(ticket)
+}
+
+fn remove_withdrawal_ticket(deps: DepsMut, ticket: &WithdrawalTicket) {
+    withdrawal_tickets_mut(deps.storage).remove(&ticket.id.to_be_bytes());
+    withdrawal_ticket_owners_mut(deps.storage, &ticket.owner).remove(&ticket.id.to_be_bytes());
+}
+
+pub fn execute_process_withdrawals(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    // Anchor is no longer used, ticket owners can cancel & redeem pro-rata
+    assert_not_exited(deps.storage)?;
+
+    // Take tickets from the front of the queue
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let tickets = withdrawal_tickets_read(deps.storage)
+        .range(None, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, ticket)| ticket))
+        .collect::<StdResult<Vec<_>>>()?;
+    if tickets.is_empty() {
+        return Err(ContractError::WithdrawalQueueEmpty {});
+    }
+
+    let total_burn_amount: Uint128 = tickets.iter().map(|ticket| ticket.burn_amount).sum();
+
+    let contract_balance = query_native_balance(
+        deps.as_ref(),
+        env.contract.address,
+        config.stable_denom.clone(),
+    )?;
+
+    // Save data for reply handler
+    pending_process_withdrawals_mut(deps.storage).save(&PendingProcessWithdrawals {
+        prev_stable_balance: contract_balance,
+        ticket_ids: tickets.iter().map(|ticket| ticket.id).collect(),
+    })?;
+
+    // Redeem all tickets in a single Anchor redeem
+    let anchor_redeem_res =
+        yield_strategy(&config).redeem_stable(total_burn_amount, PROCESS_WITHDRAWALS_REPLY_ID)?;
+    Ok(Response::new()
+        .add_submessages(anchor_redeem_res.messages)
+        .add_attributes(anchor_redeem_res.attributes)
+        .add_attribute("ticket_count", tickets.len().to_string())
+        .add_attribute("burn_amount", total_burn_amount))
+}
+
+pub fn handle_reply_process_withdrawals(
+    mut deps: DepsMut,
+    env: Env,
+    result: ContractResult<SubMsgExecutionResponse>,
+) -> Result<Response, ContractError> {
+    let config = config_read(deps.storage).load()?;
+
+    // Retrieve & clear saved data
+    let mut pending_process_withdrawals = pending_process_withdrawals_mut(deps.storage);
+    let PendingProcessWithdrawals {
+        prev_stable_balance,
+        ticket_ids,
+    } = pending_process_withdrawals.load()?;
+    pending_process_withdrawals.remove();
+
+    match result {
+        ContractResult::Ok(..) => {
+            let new_stable_balance = query_native_balance(
+                deps.as_ref(),
+                env.contract.address.clone(),
+                config.stable_denom.clone(),
+            )?;
+
+            // Difference is the stable amount redeemed from Anchor
+            let stable_amount = new_stable_balance - prev_stable_balance;
+
+            let tickets = ticket_ids
+                .iter()
+                .map(|id| withdrawal_tickets_read(deps.storage).load(&id.to_be_bytes()))
+                .collect::<StdResult<Vec<_>>>()?;
+            let total_burn_amount: Uint128 = tickets.iter().map(|ticket| ticket.burn_amount).sum();
+
+            // Burn escrowed aliceUST held by the contract itself
+            execute_burn(
+                deps.branch(),
+                env.clone(),
+                MessageInfo {
+                    sender: env.contract.address,
+                    funds: vec![],
+                },
+                total_burn_amount,
+            )?;
+
+            // Split redeemed stablecoins pro-rata, last ticket receives the rounding remainder
+            let mut response = Response::new();
+            let mut remaining_stable_amount = stable_amount;
+            for (i, ticket) in tickets.iter().enumerate() {
+                let ticket_stable_amount = if i == tickets.len() - 1 {
+                    remaining_stable_amount
+                } else {
+                    stable_amount.multiply_ratio(ticket.burn_amount, total_burn_amount)
+                };
+                remaining_stable_amount -= ticket_stable_amount;
+
+                remove_withdrawal_ticket(deps.branch(), ticket);
+                record_withdrawal(deps.storage, &ticket.owner, ticket_stable_amount)?;
+                if ticket_stable_amount.is_zero() {
+                    continue;
+                }
+
+                response = response.add_message(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: ticket.recipient.to_string(),
+                    amount: vec![deduct_tax(
+                        deps.as_ref(),
+                        Coin {
+                            denom: config.stable_denom.clone(),
+                            amount: ticket_stable_amount,
+                        },
+                    )?],
+                }));
+            }
+
+            Ok(response
+                .add_attribute("stable_amount", stable_amount)
+                .add_attribute("burn_amount", total_burn_amount))
+        }
+        ContractResult::Err(e) => Err(ContractError::Std(StdError::generic_err(e))),
+    }
+}
+
+pub fn execute_cancel_withdrawal(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let ticket = withdrawal_tickets_read(deps.storage)
+        .may_load(&id.to_be_bytes())?
+        .ok_or(ContractError::WithdrawalTicketNotFound {})?;
+    if info.sender != ticket.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    remove_withdrawal_ticket(deps.branch(), &ticket);
+
+    // Return escrowed aliceUST to the ticket owner
+    execute_transfer(
+        deps,
+        env.clone(),
+        MessageInfo {
+            sender: env.contract.address,
+            funds: vec![],
+        },
+        ticket.owner.to_string(),
+        ticket.burn_amount,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("withdrawal_ticket_id", id.to_string())
+        .add_attribute("returned_amount", ticket.burn_amount))
+}
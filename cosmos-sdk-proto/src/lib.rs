@@ -38,8 +38,25 @@ pub mod cosmos {
 
     /// Base functionality.
     pub mod base {
+        /// Query support.
+        pub mod query {
+            pub mod v1beta1 {
+                include!("prost/cosmos.base.query.v1beta1.rs");
+            }
+        }
+
         pub mod v1beta1 {
             include!("prost/cosmos.base.v1beta1.rs");
         }
     }
 }
+
+/// Terra protobuf definitions.
+pub mod terra {
+    /// Swaps between Terra stablecoins & Luna.
+    pub mod market {
+        pub mod v1beta1 {
+            include!("prost/terra.market.v1beta1.rs");
+        }
+    }
+}
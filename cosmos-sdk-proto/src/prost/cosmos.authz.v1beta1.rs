@@ -18,3 +18,48 @@ pub struct MsgExec {
     #[prost(message, repeated, tag = "2")]
     pub msgs: ::prost::alloc::vec::Vec<::prost_types::Any>,
 }
+
+/// GenericAuthorization gives the grantee unrestricted permissions to execute
+/// the provided method on behalf of the granter's account.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GenericAuthorization {
+    /// Msg, identified by it's type URL, to grant unrestricted permissions to execute
+    #[prost(string, tag = "1")]
+    pub msg: ::prost::alloc::string::String,
+}
+
+/// Grant gives permissions to execute
+/// the provide method with expiration time.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Grant {
+    #[prost(message, optional, tag = "1")]
+    pub authorization: ::core::option::Option<::prost_types::Any>,
+    #[prost(message, optional, tag = "2")]
+    pub expiration: ::core::option::Option<::prost_types::Timestamp>,
+}
+
+/// QueryGrantsRequest is the request type for the Query/Grants RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryGrantsRequest {
+    #[prost(string, tag = "1")]
+    pub granter: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub grantee: ::prost::alloc::string::String,
+    /// Optional, msg_type_url, when set, will query only grants matching given msg type.
+    #[prost(string, tag = "3")]
+    pub msg_type_url: ::prost::alloc::string::String,
+    /// pagination defines an pagination for the request.
+    #[prost(message, optional, tag = "4")]
+    pub pagination: ::core::option::Option<super::super::base::query::v1beta1::PageRequest>,
+}
+
+/// QueryGrantsResponse is the response type for the Query/Authorizations RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryGrantsResponse {
+    /// authorizations is a list of grants granted for grantee by granter.
+    #[prost(message, repeated, tag = "1")]
+    pub grants: ::prost::alloc::vec::Vec<Grant>,
+    /// pagination defines an pagination for the response.
+    #[prost(message, optional, tag = "2")]
+    pub pagination: ::core::option::Option<super::super::base::query::v1beta1::PageResponse>,
+}
@@ -0,0 +1,10 @@
+/// MsgSwap represents a message to swap coin to another denom.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSwap {
+    #[prost(string, tag = "1")]
+    pub trader: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub offer_coin: ::core::option::Option<super::super::super::cosmos::base::v1beta1::Coin>,
+    #[prost(string, tag = "3")]
+    pub ask_denom: ::prost::alloc::string::String,
+}